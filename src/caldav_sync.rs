@@ -0,0 +1,119 @@
+//! Pushes events to a remote CalDAV collection, so `publish_caldav` can act
+//! as a writer into a shared calendar rather than only exporting a static
+//! iCal string. Each event is serialized as its own single-`VEVENT`
+//! `VCALENDAR` and PUT to `<collection_url>/<uid>.ics`, where `<uid>` is the
+//! event's own `EventId` — a stable, deterministic resource name across
+//! republishes of the same event.
+
+use crate::calendar::event::Event;
+use crate::error::TempoError;
+use crate::ical_bridge;
+
+/// What happened when PUTting a single event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PutOutcome {
+    /// No ETag was known for this UID, so the PUT was conditioned on
+    /// `If-None-Match: *` and the server had nothing there yet.
+    Created,
+    /// An ETag was known for this UID, so the PUT was conditioned on
+    /// `If-Match: <etag>` and the server accepted it.
+    Updated,
+    /// The precondition failed (`412`): someone else created or modified
+    /// this resource since we last saw it.
+    Conflict,
+}
+
+pub struct PutResult {
+    pub uid: String,
+    pub outcome: PutOutcome,
+    /// The server's new ETag for this resource, if it returned one.
+    pub etag: Option<String>,
+}
+
+pub struct DeleteResult {
+    pub uid: String,
+}
+
+fn resource_url(collection_url: &str, uid: &str) -> String {
+    format!("{}/{}.ics", collection_url.trim_end_matches('/'), uid)
+}
+
+/// PUT `event` to `<collection_url>/<uid>.ics`.
+///
+/// Sends `If-Match: <known_etag>` when one is given (we've published this
+/// event before and only want to overwrite that exact server-side version),
+/// or `If-None-Match: *` otherwise (first publish of this UID — don't
+/// clobber a resource someone else already created there). A `412
+/// Precondition Failed` is reported back as `PutOutcome::Conflict` rather
+/// than an error, so the rest of the batch can still go through.
+pub async fn put_event(
+    collection_url: &str,
+    event: &Event,
+    known_etag: Option<&str>,
+) -> Result<PutResult, TempoError> {
+    let uid = event.id.0.to_string();
+    let url = resource_url(collection_url, &uid);
+    let body = ical_bridge::events_to_ical(std::slice::from_ref(event));
+
+    let client = reqwest::Client::new();
+    let mut request = client
+        .put(&url)
+        .header(reqwest::header::CONTENT_TYPE, "text/calendar; charset=utf-8")
+        .body(body);
+    request = match known_etag {
+        Some(etag) => request.header(reqwest::header::IF_MATCH, etag),
+        None => request.header(reqwest::header::IF_NONE_MATCH, "*"),
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| TempoError::SubscriptionFailed(format!("PUT {} failed: {}", url, e)))?;
+
+    if response.status() == reqwest::StatusCode::PRECONDITION_FAILED {
+        return Ok(PutResult {
+            uid,
+            outcome: PutOutcome::Conflict,
+            etag: None,
+        });
+    }
+
+    let response = response
+        .error_for_status()
+        .map_err(|e| TempoError::SubscriptionFailed(format!("PUT {} failed: {}", url, e)))?;
+
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let outcome = if known_etag.is_some() {
+        PutOutcome::Updated
+    } else {
+        PutOutcome::Created
+    };
+
+    Ok(PutResult { uid, outcome, etag })
+}
+
+/// DELETE `<collection_url>/<uid>.ics`. A `404` is treated the same as a
+/// successful delete, since the goal is just for the resource to be gone.
+pub async fn delete_event(collection_url: &str, uid: &str) -> Result<DeleteResult, TempoError> {
+    let url = resource_url(collection_url, uid);
+    let client = reqwest::Client::new();
+    let response = client
+        .delete(&url)
+        .send()
+        .await
+        .map_err(|e| TempoError::SubscriptionFailed(format!("DELETE {} failed: {}", url, e)))?;
+
+    if response.status() != reqwest::StatusCode::NOT_FOUND {
+        response
+            .error_for_status()
+            .map_err(|e| TempoError::SubscriptionFailed(format!("DELETE {} failed: {}", url, e)))?;
+    }
+
+    Ok(DeleteResult {
+        uid: uid.to_string(),
+    })
+}
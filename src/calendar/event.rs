@@ -1,9 +1,18 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 use uuid::Uuid;
 
+use crate::error::TempoError;
+
+/// Parse an IANA timezone name (e.g. `"America/New_York"`), used to anchor
+/// recurrence expansion in local wall-clock time.
+pub fn parse_iana_tz(s: &str) -> Result<chrono_tz::Tz, TempoError> {
+    s.parse()
+        .map_err(|_| TempoError::InvalidInput(format!("Invalid IANA timezone: '{}'", s)))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct EventId(pub Uuid);
 
@@ -22,17 +31,173 @@ impl fmt::Display for EventId {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecurrenceRule {
     pub rrule: String,
+    /// Instance start times (in UTC) excluded from the series, from one or
+    /// more `EXDATE` properties.
+    pub exdates: Vec<DateTime<Utc>>,
+    /// Extra one-off instance start times (in UTC) spliced into the series,
+    /// from one or more `RDATE` properties. Each uses the master event's
+    /// duration unless overridden.
+    #[serde(default)]
+    pub rdates: Vec<DateTime<Utc>>,
+}
+
+/// A single modified instance of a recurring event — iCal's `RECURRENCE-ID`
+/// mechanism for representing "this one meeting in the series was moved (or
+/// renamed/rescheduled) without touching the rest of the series".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurrenceOverride {
+    pub title: String,
+    pub start: EventTime,
+    pub end: EventTime,
+    pub metadata: HashMap<String, String>,
+    /// If set, this occurrence is cancelled (iCal `STATUS:CANCELLED` on a
+    /// `RECURRENCE-ID` instance): it's dropped from the expanded series
+    /// entirely, while the rest of the series is untouched. `title`/`start`/
+    /// `end`/`metadata` are ignored when cancelled.
+    #[serde(default)]
+    pub cancelled: bool,
+}
+
+/// An invitee on an event, with their RSVP state. Mirrors RFC 5545's
+/// `ATTENDEE` property: a `mailto:` value plus the `CN` and `PARTSTAT`
+/// parameters most schedulers care about.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Attendee {
+    pub email: String,
+    pub name: Option<String>,
+    /// Participation status, e.g. `"ACCEPTED"`, `"DECLINED"`, `"NEEDS-ACTION"`.
+    pub partstat: Option<String>,
+}
+
+/// Either a concrete instant (a timed event) or a bare calendar date (an all-day event).
+///
+/// Mirrors the DATE vs DATE-TIME distinction RFC 5545 makes for `DTSTART`/`DTEND`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EventTime {
+    DateTime(DateTime<Utc>),
+    Date(NaiveDate),
+}
+
+impl EventTime {
+    pub fn is_all_day(&self) -> bool {
+        matches!(self, EventTime::Date(_))
+    }
+
+    /// Render as the ISO 8601 form a caller would have supplied: an RFC 3339
+    /// instant for `DateTime`, or a bare `YYYY-MM-DD` for `Date`.
+    pub fn to_field_string(self) -> String {
+        match self {
+            EventTime::DateTime(dt) => dt.to_rfc3339(),
+            EventTime::Date(d) => d.format("%Y-%m-%d").to_string(),
+        }
+    }
+
+    /// Resolve to the concrete UTC instant this bound represents when used as a range *start*.
+    /// An all-day date starts at 00:00 on that day.
+    pub fn as_start_instant(&self) -> DateTime<Utc> {
+        match self {
+            EventTime::DateTime(dt) => *dt,
+            EventTime::Date(d) => d.and_time(NaiveTime::MIN).and_utc(),
+        }
+    }
+
+    /// Resolve to the concrete UTC instant this bound represents when used as a range *end*.
+    /// An all-day date ends at 24:00 (i.e. midnight of the following day), per the
+    /// `[00:00, 24:00)` half-open convention for all-day events.
+    pub fn as_end_instant(&self) -> DateTime<Utc> {
+        match self {
+            EventTime::DateTime(dt) => *dt,
+            EventTime::Date(d) => d
+                .checked_add_days(Days::new(1))
+                .unwrap_or(*d)
+                .and_time(NaiveTime::MIN)
+                .and_utc(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
     pub id: EventId,
+    /// A stable identifier from outside our own `EventId` space — e.g. an
+    /// iCal `UID`, used to recognize "this is the same event" across
+    /// import/export/re-sync instead of minting a fresh `EventId` (and
+    /// therefore a duplicate) every time. `None` for an event that has never
+    /// round-tripped through an external source.
+    #[serde(default)]
+    pub uid: Option<String>,
     pub title: String,
-    pub start: DateTime<Utc>,
-    pub end: DateTime<Utc>,
+    pub start: EventTime,
+    pub end: EventTime,
     pub timezone: String,
     pub recurrence: Option<RecurrenceRule>,
+    /// Invitees and their RSVP state. Round-tripped from/to repeated `ATTENDEE`
+    /// properties on iCal import/export; everything else iCal-specific
+    /// (description, location, organizer, status, categories) lives in `metadata`.
+    pub attendees: Vec<Attendee>,
     pub metadata: HashMap<String, String>,
+    /// Modified instances of this series, keyed by the original (un-shifted)
+    /// occurrence start they replace. Empty for a non-recurring event.
+    pub overrides: HashMap<DateTime<Utc>, RecurrenceOverride>,
+}
+
+impl Event {
+    /// The resolved UTC instant this event's busy interval begins at.
+    pub fn start_utc(&self) -> DateTime<Utc> {
+        self.start.as_start_instant()
+    }
+
+    /// The resolved UTC instant this event's busy interval ends at (exclusive).
+    pub fn end_utc(&self) -> DateTime<Utc> {
+        self.end.as_end_instant()
+    }
+
+    pub fn is_all_day(&self) -> bool {
+        self.start.is_all_day()
+    }
+
+    /// Resolve the `timezone` field to a concrete IANA timezone, used to
+    /// anchor recurrence expansion in local wall-clock time.
+    pub fn timezone_tz(&self) -> Result<chrono_tz::Tz, TempoError> {
+        parse_iana_tz(&self.timezone)
+    }
+}
+
+/// How an occurrence affects free/busy calculations, mirroring the
+/// busy/tentative/out-of-office semantics shared calendars use. Derived from
+/// `metadata`'s iCal-sourced `status` and `transp` keys; defaults to `Busy`
+/// when neither is present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Transparency {
+    /// Blocks the time. The default.
+    Busy,
+    /// Soft-blocks the time; surfaced separately so callers can decide whether it counts.
+    Tentative,
+    /// Doesn't block the time at all (iCal `TRANSP:TRANSPARENT`).
+    Free,
+    OutOfOffice,
+}
+
+impl Transparency {
+    /// Classify from an event's `metadata`: `transp: "TRANSPARENT"` wins as
+    /// `Free` regardless of status, otherwise `status` of `"TENTATIVE"` or
+    /// `"X-OOF"`/`"OUT-OF-OFFICE"` maps to the matching variant, and anything
+    /// else defaults to `Busy`.
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> Self {
+        if metadata
+            .get("transp")
+            .is_some_and(|t| t.eq_ignore_ascii_case("TRANSPARENT"))
+        {
+            return Transparency::Free;
+        }
+        match metadata.get("status").map(|s| s.to_ascii_uppercase()).as_deref() {
+            Some("TENTATIVE") => Transparency::Tentative,
+            Some("X-OOF") | Some("OUT-OF-OFFICE") => Transparency::OutOfOffice,
+            _ => Transparency::Busy,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
@@ -42,6 +207,8 @@ pub struct EventOccurrence {
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
     pub is_recurring: bool,
+    pub is_all_day: bool,
+    pub transparency: Transparency,
     pub metadata: HashMap<String, String>,
 }
 
@@ -50,9 +217,11 @@ impl Event {
         EventOccurrence {
             event_id: self.id,
             title: self.title.clone(),
-            start: self.start,
-            end: self.end,
+            start: self.start_utc(),
+            end: self.end_utc(),
             is_recurring: self.recurrence.is_some(),
+            is_all_day: self.is_all_day(),
+            transparency: Transparency::from_metadata(&self.metadata),
             metadata: self.metadata.clone(),
         }
     }
@@ -1,25 +1,102 @@
 pub mod event;
 pub mod proposal;
+pub mod query;
+pub mod recurrence;
 pub mod time_utils;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use chrono::{DateTime, TimeDelta, Utc};
+use chrono_tz::Tz;
 
 use crate::error::TempoError;
-use event::{Event, EventId, EventOccurrence};
+use event::{Event, EventId, EventOccurrence, RecurrenceOverride, RecurrenceRule, Transparency};
 use proposal::{
     ConflictReport, Proposal, ProposalId, ProposedEvent, detect_conflicts,
 };
-use time_utils::{FreeBusyResult, TimeRange, compute_free_busy, find_free_slots};
+use time_utils::{
+    AgendaDay, FreeBusyResult, LocatedBusyPeriod, MutualFreeSlot, TimeRange, TravelAwareSlot,
+    TravelMatrix, WeeklyAvailability, build_agenda, compute_free_busy, find_free_slots,
+    find_free_slots_with_travel, find_free_slots_within_hours, find_mutual_free_slots,
+    find_mutual_free_slots_with_counts,
+};
 
 const MAX_RECURRENCE_OCCURRENCES: u16 = 1000;
 
+/// Tracks a calendar's remote iCal subscription so `refresh_calendar` can
+/// issue a conditional GET and skip re-parsing when the feed hasn't changed.
+#[derive(Debug, Clone)]
+pub struct SyncState {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    body_hash: u64,
+}
+
+impl SyncState {
+    pub fn new(url: String, etag: Option<String>, last_modified: Option<String>, body: &str) -> Self {
+        Self {
+            url,
+            etag,
+            last_modified,
+            body_hash: hash_body(body),
+        }
+    }
+
+    /// True if `body` hashes the same as the body this state was last built
+    /// from — i.e. a `200` response that didn't actually change anything, so
+    /// a refresh should report `updated: false` rather than replacing events
+    /// (and minting new IDs for them) with no-op duplicates.
+    pub fn body_unchanged(&self, body: &str) -> bool {
+        hash_body(body) == self.body_hash
+    }
+}
+
+/// Tracks a calendar's Google Calendar sync cursor, so a follow-up
+/// `sync_google_calendar` call can pass `sync_token` for an incremental
+/// delta fetch instead of re-fetching the whole window.
+#[derive(Debug, Clone)]
+pub struct GoogleSyncState {
+    pub calendar_id: String,
+    pub sync_token: Option<String>,
+}
+
+/// Tracks a calendar's last `publish_caldav` run against a given collection,
+/// so the next publish knows which change-log `seq` to diff from (for the
+/// DELETE pass) and which `ETag` to send as `If-Match` for each event (to
+/// detect a conflicting edit made directly on the server).
+#[derive(Debug, Clone)]
+pub struct CaldavPublishState {
+    pub collection_url: String,
+    pub last_seq: u64,
+    pub etags: HashMap<EventId, String>,
+}
+
+fn hash_body(body: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    body.hash(&mut hasher);
+    hasher.finish()
+}
+
 #[derive(Debug)]
 pub struct Calendar {
     #[allow(dead_code)] // stored for debugging/display
     name: String,
     events: HashMap<EventId, Event>,
+    /// IDs of non-recurring events, maintained alongside `events` so
+    /// `occurrences_iter` can skip straight to an overlap check for these —
+    /// no RRULE parsing at all — rather than branching on `recurrence` for
+    /// every event on every query.
+    fixed_ids: HashSet<EventId>,
+    /// IDs of recurring events — the only ones that need RRULE expansion.
+    repeating_ids: HashSet<EventId>,
+    /// Maps an event's external `uid` to its `EventId`, so `get_by_uid` and
+    /// `upsert_by_uid` don't need to scan every event.
+    uid_index: HashMap<String, EventId>,
+    subscription: Option<SyncState>,
+    google_sync: Option<GoogleSyncState>,
+    caldav_publish: Option<CaldavPublishState>,
 }
 
 impl Calendar {
@@ -27,25 +104,207 @@ impl Calendar {
         Self {
             name,
             events: HashMap::new(),
+            fixed_ids: HashSet::new(),
+            repeating_ids: HashSet::new(),
+            uid_index: HashMap::new(),
+            subscription: None,
+            google_sync: None,
+            caldav_publish: None,
+        }
+    }
+
+    /// Record `event` in the fixed/repeating partition and the `uid` index.
+    /// Must be called for every event added to `events`.
+    fn index_event(&mut self, event: &Event) {
+        if event.recurrence.is_some() {
+            self.repeating_ids.insert(event.id);
+        } else {
+            self.fixed_ids.insert(event.id);
+        }
+        if let Some(uid) = &event.uid {
+            self.uid_index.insert(uid.clone(), event.id);
+        }
+    }
+
+    /// Undo `index_event`. Must be called for every event removed from `events`.
+    fn deindex_event(&mut self, event: &Event) {
+        self.fixed_ids.remove(&event.id);
+        self.repeating_ids.remove(&event.id);
+        if let Some(uid) = &event.uid {
+            self.uid_index.remove(uid);
         }
     }
 
     pub fn add_event(&mut self, event: Event) -> EventId {
         let id = event.id;
+        self.index_event(&event);
         self.events.insert(id, event);
         id
     }
 
+    /// Add `event`, replacing any existing event with the same
+    /// `google_calendar_id` metadata value. Used by incremental Google
+    /// Calendar sync, where an updated event arrives as a fresh copy rather
+    /// than a patch, so matching on the stable Google ID (not our own
+    /// `EventId`, which is re-minted on every conversion) is what keeps a
+    /// re-synced event from appearing twice.
+    pub fn upsert_event_by_google_id(&mut self, event: Event) -> EventId {
+        if let Some(google_id) = event.metadata.get("google_calendar_id").cloned() {
+            let stale: Vec<EventId> = self
+                .events
+                .values()
+                .filter(|e| e.metadata.get("google_calendar_id") == Some(&google_id))
+                .map(|e| e.id)
+                .collect();
+            for id in stale {
+                self.remove_event(&id);
+            }
+        }
+        self.add_event(event)
+    }
+
+    /// Add `event`, replacing any existing event with the same `uid` rather
+    /// than duplicating it. Mirrors `upsert_event_by_google_id`, but keyed on
+    /// our own stable `uid` field instead of a provider-specific metadata
+    /// value — e.g. for a CalDAV/iCal re-import, where re-parsing always
+    /// mints a fresh `EventId` for what may be the same series.
+    pub fn upsert_by_uid(&mut self, event: Event) -> EventId {
+        if let Some(uid) = &event.uid {
+            if let Some(&existing_id) = self.uid_index.get(uid) {
+                self.remove_event(&existing_id);
+            }
+        }
+        self.add_event(event)
+    }
+
+    /// Look up an event by its external `uid`.
+    pub fn get_by_uid(&self, uid: &str) -> Option<&Event> {
+        self.uid_index.get(uid).and_then(|id| self.events.get(id))
+    }
+
+    /// Remove events whose `google_calendar_id` metadata is in `ids`. Used to
+    /// apply Google Calendar's `status: "cancelled"` deletions from an
+    /// incremental sync. Returns the number of events removed.
+    pub fn remove_events_by_google_id(&mut self, ids: &[String]) -> usize {
+        let stale: Vec<EventId> = self
+            .events
+            .values()
+            .filter(|e| e.metadata.get("google_calendar_id").is_some_and(|g| ids.contains(g)))
+            .map(|e| e.id)
+            .collect();
+        for id in &stale {
+            self.remove_event(id);
+        }
+        stale.len()
+    }
+
     pub fn remove_event(&mut self, id: &EventId) -> Option<Event> {
-        self.events.remove(id)
+        let event = self.events.remove(id)?;
+        self.deindex_event(&event);
+        Some(event)
+    }
+
+    /// Materialize a one-off edit to a single occurrence of a recurring
+    /// series, keyed by its original (un-shifted) start — iCal's
+    /// `RECURRENCE-ID` mechanism. The rest of the series is untouched.
+    pub fn detach_occurrence(
+        &mut self,
+        event_id: EventId,
+        original_start: DateTime<Utc>,
+        new_fields: RecurrenceOverride,
+    ) -> Result<(), TempoError> {
+        let event = self.recurring_event_mut(event_id)?;
+        event.overrides.insert(original_start, new_fields);
+        Ok(())
+    }
+
+    /// Cancel a single occurrence of a recurring series, keyed by its
+    /// original start, without touching the rest of the series.
+    pub fn cancel_occurrence(
+        &mut self,
+        event_id: EventId,
+        original_start: DateTime<Utc>,
+    ) -> Result<(), TempoError> {
+        let event = self.recurring_event_mut(event_id)?;
+        event.overrides.insert(
+            original_start,
+            RecurrenceOverride {
+                title: event.title.clone(),
+                start: event.start,
+                end: event.end,
+                metadata: event.metadata.clone(),
+                cancelled: true,
+            },
+        );
+        Ok(())
+    }
+
+    fn recurring_event_mut(&mut self, event_id: EventId) -> Result<&mut Event, TempoError> {
+        let event = self
+            .events
+            .get_mut(&event_id)
+            .ok_or_else(|| TempoError::EventNotFound(event_id.to_string()))?;
+        if event.recurrence.is_none() {
+            return Err(TempoError::InvalidInput(format!(
+                "Event {} is not recurring; cannot edit a single occurrence of it",
+                event_id
+            )));
+        }
+        Ok(event)
     }
 
     pub fn events(&self) -> impl Iterator<Item = &Event> {
         self.events.values()
     }
 
+    /// Non-recurring events — the subset `occurrences_iter` can test for
+    /// overlap directly, with no RRULE expansion.
+    pub fn fixed_events(&self) -> impl Iterator<Item = &Event> {
+        self.fixed_ids.iter().filter_map(|id| self.events.get(id))
+    }
+
+    /// Recurring events — the only subset `occurrences_iter` needs to expand.
+    pub fn repeating_events(&self) -> impl Iterator<Item = &Event> {
+        self.repeating_ids.iter().filter_map(|id| self.events.get(id))
+    }
+
     pub fn clear(&mut self) {
         self.events.clear();
+        self.fixed_ids.clear();
+        self.repeating_ids.clear();
+        self.uid_index.clear();
+    }
+
+    /// Replace all events wholesale (e.g. after re-parsing a refreshed feed).
+    pub fn replace_events(&mut self, events: Vec<Event>) {
+        self.clear();
+        for event in events {
+            self.add_event(event);
+        }
+    }
+
+    pub fn sync_state(&self) -> Option<&SyncState> {
+        self.subscription.as_ref()
+    }
+
+    pub fn set_sync_state(&mut self, state: SyncState) {
+        self.subscription = Some(state);
+    }
+
+    pub fn google_sync_state(&self) -> Option<&GoogleSyncState> {
+        self.google_sync.as_ref()
+    }
+
+    pub fn set_google_sync_state(&mut self, state: GoogleSyncState) {
+        self.google_sync = Some(state);
+    }
+
+    pub fn caldav_publish_state(&self) -> Option<&CaldavPublishState> {
+        self.caldav_publish.as_ref()
+    }
+
+    pub fn set_caldav_publish_state(&mut self, state: CaldavPublishState) {
+        self.caldav_publish = Some(state);
     }
 
     pub fn occurrences_in_range(
@@ -53,37 +312,230 @@ impl Calendar {
         start: DateTime<Utc>,
         end: DateTime<Utc>,
     ) -> Result<Vec<EventOccurrence>, TempoError> {
-        let mut occurrences = Vec::new();
-        for event in self.events.values() {
-            let mut event_occs = expand_event(event, start, end)?;
-            occurrences.append(&mut event_occs);
+        Ok(self.occurrences_iter(start, end)?.collect())
+    }
+
+    /// As `occurrences_in_range`, but also reports whether any event's
+    /// recurrence hit the `MAX_RECURRENCE_OCCURRENCES` safety bound within
+    /// `[start, end)` — a caller doing a long-range agenda or free/busy scan
+    /// can use this to detect an incomplete result instead of silently
+    /// treating a truncated series as exhaustive.
+    pub fn occurrences_in_range_truncated(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<(Vec<EventOccurrence>, bool), TempoError> {
+        let iter = self.occurrences_iter(start, end)?;
+        let truncated = iter.truncated();
+        Ok((iter.collect(), truncated))
+    }
+
+    /// Lazily merge every event's (individually sorted, and individually
+    /// bounded to `MAX_RECURRENCE_OCCURRENCES`) occurrence stream into one
+    /// chronologically ordered stream, without first collecting the full
+    /// cross product of events x occurrences into one `Vec` and sorting it.
+    /// `OccurrencesIter::truncated` reports whether any contributing event
+    /// hit its safety bound.
+    ///
+    /// Fixed events are tested for overlap directly — no RRULE machinery
+    /// involved — and only `repeating_events` go through `expand_event`.
+    pub fn occurrences_iter(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<OccurrencesIter, TempoError> {
+        let mut streams = Vec::new();
+        for event in self.fixed_events() {
+            if let Some(occ) = fixed_event_occurrence(event, start, end) {
+                streams.push(vec![occ].into_iter().peekable());
+            }
+        }
+
+        let mut truncated = false;
+        for event in self.repeating_events() {
+            let (occs, event_truncated) = expand_event(event, start, end)?;
+            truncated |= event_truncated;
+            if !occs.is_empty() {
+                streams.push(occs.into_iter().peekable());
+            }
+        }
+        Ok(OccurrencesIter { streams, truncated })
+    }
+}
+
+/// Returned by `Calendar::occurrences_iter`. Yields occurrences across every
+/// event in chronological order by repeatedly pulling the earliest-starting
+/// occurrence off whichever event stream has it next.
+pub struct OccurrencesIter {
+    streams: Vec<std::iter::Peekable<std::vec::IntoIter<EventOccurrence>>>,
+    truncated: bool,
+}
+
+impl OccurrencesIter {
+    /// Whether any contributing event's recurrence hit the
+    /// `MAX_RECURRENCE_OCCURRENCES` safety bound within the query range.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl Iterator for OccurrencesIter {
+    type Item = EventOccurrence;
+
+    fn next(&mut self) -> Option<EventOccurrence> {
+        let mut next_idx = None;
+        for (idx, stream) in self.streams.iter_mut().enumerate() {
+            let Some(candidate) = stream.peek() else {
+                continue;
+            };
+            let is_earlier = match next_idx {
+                None => true,
+                Some((_, earliest_start)) => candidate.start < earliest_start,
+            };
+            if is_earlier {
+                next_idx = Some((idx, candidate.start));
+            }
         }
-        occurrences.sort_by_key(|o| o.start);
-        Ok(occurrences)
+        let (idx, _) = next_idx?;
+        self.streams[idx].next()
     }
 }
 
-/// Expand an event (possibly recurring) into concrete occurrences within a range.
+/// A fixed (non-recurring) event's single occurrence, if it overlaps
+/// `[range_start, range_end)` — no RRULE parsing involved.
+fn fixed_event_occurrence(
+    event: &Event,
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+) -> Option<EventOccurrence> {
+    let event_range = TimeRange::new(event.start_utc(), event.end_utc());
+    let query_range = TimeRange::new(range_start, range_end);
+    event_range.overlaps(&query_range).then(|| event.to_occurrence())
+}
+
+/// Expand a recurring event's RRULE into concrete occurrences within a
+/// range, plus whether it hit the `MAX_RECURRENCE_OCCURRENCES` safety bound.
+/// Only meaningful for an event with `recurrence: Some(_)`; a non-recurring
+/// event is handled by `fixed_event_occurrence` instead.
 fn expand_event(
     event: &Event,
     range_start: DateTime<Utc>,
     range_end: DateTime<Utc>,
-) -> Result<Vec<EventOccurrence>, TempoError> {
+) -> Result<(Vec<EventOccurrence>, bool), TempoError> {
     let Some(ref recurrence) = event.recurrence else {
-        // Non-recurring: include if it overlaps the range
-        let event_range = TimeRange::new(event.start, event.end);
+        return Ok(match fixed_event_occurrence(event, range_start, range_end) {
+            Some(occ) => (vec![occ], false),
+            None => (vec![], false),
+        });
+    };
+
+    let tz = event.timezone_tz()?;
+    let start_utc = event.start_utc();
+    let duration = event.end_utc() - start_utc;
+    let (starts, truncated) =
+        recurrence_starts_in_range(start_utc, tz, recurrence, range_start, range_end)?;
+
+    let occurrences = starts
+        .into_iter()
+        .filter_map(|start_utc| match event.overrides.get(&start_utc) {
+            Some(over) if over.cancelled => None,
+            Some(over) => Some(EventOccurrence {
+                event_id: event.id,
+                title: over.title.clone(),
+                start: over.start.as_start_instant(),
+                end: over.end.as_end_instant(),
+                is_recurring: true,
+                is_all_day: over.start.is_all_day(),
+                transparency: Transparency::from_metadata(&over.metadata),
+                metadata: over.metadata.clone(),
+            }),
+            None => Some(EventOccurrence {
+                event_id: event.id,
+                title: event.title.clone(),
+                start: start_utc,
+                end: start_utc + duration,
+                is_recurring: true,
+                is_all_day: event.is_all_day(),
+                transparency: Transparency::from_metadata(&event.metadata),
+                metadata: event.metadata.clone(),
+            }),
+        })
+        .collect();
+
+    Ok((occurrences, truncated))
+}
+
+/// Expand a proposed event (possibly recurring) into concrete occurrences within a
+/// range. Mirrors `expand_event`, but proposed events have no stable `EventId` yet,
+/// so each occurrence is assigned a fresh one.
+fn expand_proposed_event(
+    proposed: &ProposedEvent,
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+) -> Result<Vec<EventOccurrence>, TempoError> {
+    let Some(ref recurrence) = proposed.recurrence else {
+        let event_range = TimeRange::new(proposed.start_utc(), proposed.end_utc());
         let query_range = TimeRange::new(range_start, range_end);
         if event_range.overlaps(&query_range) {
-            return Ok(vec![event.to_occurrence()]);
+            return Ok(vec![EventOccurrence {
+                event_id: EventId::new(),
+                title: proposed.title.clone(),
+                start: proposed.start_utc(),
+                end: proposed.end_utc(),
+                is_recurring: false,
+                is_all_day: proposed.start.is_all_day(),
+                transparency: Transparency::from_metadata(&proposed.metadata),
+                metadata: proposed.metadata.clone(),
+            }]);
         }
         return Ok(vec![]);
     };
 
-    let duration = event.end - event.start;
+    let tz = proposed.timezone_tz()?;
+    let start_utc = proposed.start_utc();
+    let duration = proposed.end_utc() - start_utc;
+    let (starts, _truncated) =
+        recurrence_starts_in_range(start_utc, tz, recurrence, range_start, range_end)?;
+
+    Ok(starts
+        .into_iter()
+        .map(|start_utc| EventOccurrence {
+            event_id: EventId::new(),
+            title: proposed.title.clone(),
+            start: start_utc,
+            end: start_utc + duration,
+            is_recurring: true,
+            is_all_day: proposed.start.is_all_day(),
+            transparency: Transparency::from_metadata(&proposed.metadata),
+            metadata: proposed.metadata.clone(),
+        })
+        .collect())
+}
 
+/// Expand an RRULE anchored at `start_utc` into the concrete occurrence start
+/// instants that fall within `[range_start, range_end)`: `EXDATE` instants are
+/// dropped, then `RDATE` instants within the range are spliced in, and the
+/// result is sorted back into chronological order. Also reports whether the
+/// RRULE itself hit the `MAX_RECURRENCE_OCCURRENCES` safety bound before
+/// `range_end` — e.g. an unbounded daily recurrence queried over many years —
+/// so a caller can surface that instead of silently returning a partial
+/// series.
+///
+/// The rule is anchored in `tz`'s local wall-clock time (via a `TZID`
+/// `DTSTART`) rather than UTC, so a recurrence keeps its local hour across
+/// DST transitions instead of drifting by the UTC offset change.
+fn recurrence_starts_in_range(
+    start_utc: DateTime<Utc>,
+    tz: Tz,
+    recurrence: &RecurrenceRule,
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+) -> Result<(Vec<DateTime<Utc>>, bool), TempoError> {
+    let local_start = start_utc.with_timezone(&tz);
     let rrule_str = format!(
-        "DTSTART:{}\nRRULE:{}",
-        event.start.format("%Y%m%dT%H%M%SZ"),
+        "DTSTART;TZID={}:{}\nRRULE:{}",
+        tz,
+        local_start.format("%Y%m%dT%H%M%S"),
         recurrence.rrule
     );
 
@@ -99,28 +551,52 @@ fn expand_event(
         .after(tz_start)
         .before(tz_end)
         .all(MAX_RECURRENCE_OCCURRENCES);
+    let truncated = result.limited;
 
-    Ok(result
+    let mut starts: Vec<DateTime<Utc>> = result
         .dates
         .into_iter()
-        .map(|dt| {
-            let start_utc = dt.with_timezone(&Utc);
-            EventOccurrence {
-                event_id: event.id,
-                title: event.title.clone(),
-                start: start_utc,
-                end: start_utc + duration,
-                is_recurring: true,
-                metadata: event.metadata.clone(),
-            }
-        })
-        .collect())
+        .map(|dt| dt.with_timezone(&Utc))
+        .filter(|start| !recurrence.exdates.contains(start))
+        .collect();
+
+    starts.extend(
+        recurrence
+            .rdates
+            .iter()
+            .filter(|rdate| **rdate >= range_start && **rdate < range_end)
+            .filter(|rdate| !starts.contains(rdate)),
+    );
+    starts.sort();
+
+    Ok((starts, truncated))
 }
 
 #[derive(Debug)]
 pub struct CalendarStore {
     calendars: HashMap<String, Calendar>,
     proposals: HashMap<ProposalId, Proposal>,
+    travel_matrix: TravelMatrix,
+    change_log: Vec<SyncChange>,
+    next_seq: u64,
+}
+
+/// Whether a `SyncChange` entry records an addition or a removal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+}
+
+/// One entry in `CalendarStore`'s append-only change log, for `get_changes`
+/// to diff against a caller's last-seen `seq` instead of re-listing every
+/// event on every turn.
+#[derive(Debug, Clone)]
+pub struct SyncChange {
+    pub seq: u64,
+    pub kind: ChangeKind,
+    pub event_id: EventId,
+    pub calendar: String,
 }
 
 impl CalendarStore {
@@ -130,7 +606,94 @@ impl CalendarStore {
         Self {
             calendars,
             proposals: HashMap::new(),
+            travel_matrix: TravelMatrix::new(),
+            change_log: Vec::new(),
+            next_seq: 0,
+        }
+    }
+
+    fn record_change(&mut self, kind: ChangeKind, event_id: EventId, calendar: &str) {
+        self.next_seq += 1;
+        self.change_log.push(SyncChange {
+            seq: self.next_seq,
+            kind,
+            event_id,
+            calendar: calendar.to_string(),
+        });
+    }
+
+    /// Add `event` to `calendar_name`, recording the addition in the change
+    /// log for `get_changes`.
+    pub fn add_event(&mut self, calendar_name: &str, event: Event) -> EventId {
+        let id = self.get_or_create_calendar(calendar_name).add_event(event);
+        self.record_change(ChangeKind::Added, id, calendar_name);
+        id
+    }
+
+    /// Remove an event from `calendar_name` by ID, recording the removal in
+    /// the change log for `get_changes`.
+    pub fn remove_event(
+        &mut self,
+        calendar_name: &str,
+        id: &EventId,
+    ) -> Result<Event, TempoError> {
+        let event = self
+            .get_calendar_mut(calendar_name)
+            .ok_or_else(|| TempoError::CalendarNotFound(calendar_name.to_string()))?
+            .remove_event(id)
+            .ok_or_else(|| TempoError::EventNotFound(id.to_string()))?;
+        self.record_change(ChangeKind::Removed, *id, calendar_name);
+        Ok(event)
+    }
+
+    /// Remove every event from `calendar_name`, recording a removal for each
+    /// in the change log for `get_changes`.
+    pub fn clear_calendar(&mut self, calendar_name: &str) -> Result<usize, TempoError> {
+        let cal = self
+            .get_calendar_mut(calendar_name)
+            .ok_or_else(|| TempoError::CalendarNotFound(calendar_name.to_string()))?;
+        let ids: Vec<EventId> = cal.events().map(|e| e.id).collect();
+        cal.clear();
+        for id in &ids {
+            self.record_change(ChangeKind::Removed, *id, calendar_name);
+        }
+        Ok(ids.len())
+    }
+
+    /// Changes with `seq` greater than `since_seq`, plus the token (the
+    /// current highest `seq`) to pass as `since_seq` on the next call.
+    ///
+    /// An event added and then removed again within the window nets to no
+    /// observable change, since a caller who hadn't seen `since_seq` yet
+    /// never saw the event exist in the first place.
+    pub fn get_changes(&self, since_seq: u64) -> (Vec<SyncChange>, u64) {
+        let mut last_seen: HashMap<(String, EventId), SyncChange> = HashMap::new();
+        let mut first_kind: HashMap<(String, EventId), ChangeKind> = HashMap::new();
+        for change in self.change_log.iter().filter(|c| c.seq > since_seq) {
+            let key = (change.calendar.clone(), change.event_id);
+            first_kind.entry(key.clone()).or_insert(change.kind);
+            last_seen.insert(key, change.clone());
         }
+
+        let mut changes: Vec<SyncChange> = last_seen
+            .into_iter()
+            .filter(|(key, last)| {
+                !(first_kind.get(key) == Some(&ChangeKind::Added)
+                    && last.kind == ChangeKind::Removed)
+            })
+            .map(|(_, last)| last)
+            .collect();
+        changes.sort_by_key(|c| c.seq);
+
+        (changes, self.next_seq)
+    }
+
+    /// Register the travel time between two locations (e.g. event
+    /// `location` metadata values), for `find_available_slots_with_travel`
+    /// to use when sizing location-aware buffers. Symmetric: applies in
+    /// both directions.
+    pub fn set_travel_time(&mut self, a: &str, b: &str, minutes: u32) {
+        self.travel_matrix.set(a, b, minutes);
     }
 
     /// Get or create a calendar by name (case-insensitive).
@@ -175,31 +738,197 @@ impl CalendarStore {
         Ok(all)
     }
 
+    /// As `occurrences_in_range`, but also reports whether any contributing
+    /// calendar's recurrence expansion hit the `MAX_RECURRENCE_OCCURRENCES`
+    /// safety bound, so a long-range agenda or free/busy caller can surface a
+    /// truncated result instead of treating it as exhaustive.
+    pub fn occurrences_in_range_truncated(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        calendar_name: Option<&str>,
+    ) -> Result<(Vec<EventOccurrence>, bool), TempoError> {
+        match calendar_name {
+            Some(name) => {
+                let cal = self
+                    .get_calendar(name)
+                    .ok_or_else(|| TempoError::CalendarNotFound(name.to_string()))?;
+                cal.occurrences_in_range_truncated(start, end)
+            }
+            None => {
+                let mut all = Vec::new();
+                let mut truncated = false;
+                for cal in self.calendars.values() {
+                    let (mut occs, cal_truncated) = cal.occurrences_in_range_truncated(start, end)?;
+                    truncated |= cal_truncated;
+                    all.append(&mut occs);
+                }
+                all.sort_by_key(|o| o.start);
+                Ok((all, truncated))
+            }
+        }
+    }
+
+    /// Free/busy analysis over `[start, end)`, plus whether any contributing
+    /// event's recurrence expansion hit the `MAX_RECURRENCE_OCCURRENCES`
+    /// safety bound — a caller doing a long-range scan should surface this
+    /// rather than treat the result as exhaustive.
     pub fn free_busy(
         &self,
         start: DateTime<Utc>,
         end: DateTime<Utc>,
         calendar_name: Option<&str>,
-    ) -> Result<FreeBusyResult, TempoError> {
-        let occs = self.occurrences_in_range(start, end, calendar_name)?;
+        count_tentative_as_busy: bool,
+    ) -> Result<(FreeBusyResult, bool), TempoError> {
+        let (occs, truncated) = self.occurrences_in_range_truncated(start, end, calendar_name)?;
         let range = TimeRange::new(start, end);
-        Ok(compute_free_busy(&occs, &range))
+        Ok((compute_free_busy(&occs, &range, count_tentative_as_busy), truncated))
     }
 
+    /// As `free_busy`, but reports open slots instead of busy ones; see
+    /// `free_busy` for what the truncation flag means.
     pub fn find_available_slots(
         &self,
         start: DateTime<Utc>,
         end: DateTime<Utc>,
         min_duration: TimeDelta,
         calendar_name: Option<&str>,
-    ) -> Result<Vec<TimeRange>, TempoError> {
-        let occs = self.occurrences_in_range(start, end, calendar_name)?;
+    ) -> Result<(Vec<TimeRange>, bool), TempoError> {
+        let (occs, truncated) = self.occurrences_in_range_truncated(start, end, calendar_name)?;
+        let busy: Vec<TimeRange> = occs
+            .iter()
+            .map(|o| TimeRange::new(o.start, o.end))
+            .collect();
+        let range = TimeRange::new(start, end);
+        Ok((find_free_slots(&busy, &range, min_duration), truncated))
+    }
+
+    /// As `find_available_slots`, but sizes each gap's leading/trailing
+    /// buffer by travel time instead of a flat amount: the buffer to/from an
+    /// adjacent event is the registered `set_travel_time` duration between
+    /// that event's `location` metadata and `slot_location`, falling back to
+    /// `default_buffer` when either location is unknown or unregistered. See
+    /// `free_busy` for what the truncation flag means.
+    pub fn find_available_slots_with_travel(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        min_duration: TimeDelta,
+        calendar_name: Option<&str>,
+        slot_location: Option<&str>,
+        default_buffer: TimeDelta,
+    ) -> Result<(Vec<TravelAwareSlot>, bool), TempoError> {
+        let (occs, truncated) = self.occurrences_in_range_truncated(start, end, calendar_name)?;
+        let busy: Vec<LocatedBusyPeriod> = occs
+            .iter()
+            .map(|o| LocatedBusyPeriod {
+                range: TimeRange::new(o.start, o.end),
+                location: o.metadata.get("location").cloned(),
+            })
+            .collect();
+        let range = TimeRange::new(start, end);
+        Ok((
+            find_free_slots_with_travel(&busy, &range, min_duration, slot_location, default_buffer, &self.travel_matrix),
+            truncated,
+        ))
+    }
+
+    /// As `find_available_slots`, but additionally restricts candidate slots
+    /// to `availability`'s recurring daily working windows (e.g. 09:00-17:00,
+    /// Mon-Fri) instead of the raw `[start, end)` range. See `free_busy` for
+    /// what the truncation flag means.
+    pub fn find_available_slots_within_hours(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        min_duration: TimeDelta,
+        calendar_name: Option<&str>,
+        availability: &WeeklyAvailability,
+    ) -> Result<(Vec<TimeRange>, bool), TempoError> {
+        let (occs, truncated) = self.occurrences_in_range_truncated(start, end, calendar_name)?;
         let busy: Vec<TimeRange> = occs
             .iter()
             .map(|o| TimeRange::new(o.start, o.end))
             .collect();
         let range = TimeRange::new(start, end);
-        Ok(find_free_slots(&busy, &range, min_duration))
+        Ok((find_free_slots_within_hours(&busy, &range, min_duration, availability)?, truncated))
+    }
+
+    /// Find slots where every named calendar is simultaneously free for at
+    /// least `min_duration` — the key primitive for scheduling a meeting
+    /// across participants, each represented by their own calendar.
+    pub fn find_mutual_free_slots(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        min_duration: TimeDelta,
+        calendar_names: &[String],
+    ) -> Result<(Vec<TimeRange>, bool), TempoError> {
+        let (busy_per_participant, truncated) =
+            self.busy_per_participant(start, end, calendar_names)?;
+        let range = TimeRange::new(start, end);
+        Ok((
+            find_mutual_free_slots(&busy_per_participant, &range, min_duration),
+            truncated,
+        ))
+    }
+
+    /// As `find_mutual_free_slots`, but reports how many participants are
+    /// free for each qualifying slot instead of requiring unanimous
+    /// availability, so a caller can surface best-effort k-of-n slots.
+    pub fn find_mutual_free_slots_with_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        min_duration: TimeDelta,
+        calendar_names: &[String],
+    ) -> Result<(Vec<MutualFreeSlot>, bool), TempoError> {
+        let (busy_per_participant, truncated) =
+            self.busy_per_participant(start, end, calendar_names)?;
+        let range = TimeRange::new(start, end);
+        Ok((
+            find_mutual_free_slots_with_counts(&busy_per_participant, &range, min_duration),
+            truncated,
+        ))
+    }
+
+    /// As `occurrences_in_range_truncated`, but per participant calendar. See
+    /// `free_busy` for what the truncation flag means.
+    fn busy_per_participant(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        calendar_names: &[String],
+    ) -> Result<(Vec<Vec<TimeRange>>, bool), TempoError> {
+        let mut truncated = false;
+        let busy = calendar_names
+            .iter()
+            .map(|name| {
+                let (occs, cal_truncated) =
+                    self.occurrences_in_range_truncated(start, end, Some(name))?;
+                truncated |= cal_truncated;
+                Ok(occs
+                    .iter()
+                    .map(|o| TimeRange::new(o.start, o.end))
+                    .collect())
+            })
+            .collect::<Result<Vec<Vec<TimeRange>>, TempoError>>()?;
+        Ok((busy, truncated))
+    }
+
+    /// Build a day-by-day agenda for a time range in `tz`. Multi-day
+    /// occurrences appear under every day they overlap. See `free_busy` for
+    /// what the truncation flag means.
+    pub fn agenda(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        tz: Tz,
+        calendar_name: Option<&str>,
+    ) -> Result<(Vec<AgendaDay>, bool), TempoError> {
+        let (occs, truncated) = self.occurrences_in_range_truncated(start, end, calendar_name)?;
+        let range = TimeRange::new(start, end);
+        Ok((build_agenda(&occs, &range, tz)?, truncated))
     }
 
     // -- Proposal methods --
@@ -235,20 +964,6 @@ impl CalendarStore {
             .get(proposal_id)
             .ok_or_else(|| TempoError::ProposalNotFound(proposal_id.to_string()))?;
 
-        // Convert proposed events to occurrences for conflict detection
-        let proposed_occs: Vec<EventOccurrence> = proposal
-            .events
-            .iter()
-            .map(|pe| EventOccurrence {
-                event_id: EventId::new(),
-                title: pe.title.clone(),
-                start: pe.start,
-                end: pe.end,
-                is_recurring: pe.recurrence.is_some(),
-                metadata: pe.metadata.clone(),
-            })
-            .collect();
-
         // Find the time range spanning all proposed events
         let Some((range_start, range_end)) = proposed_time_bounds(&proposal.events) else {
             return Ok(ConflictReport {
@@ -257,6 +972,15 @@ impl CalendarStore {
                 conflicts: vec![],
             });
         };
+
+        // Expand each proposed event (including recurring ones) into occurrences
+        // within that range, so a recurring proposal conflicts on every instance,
+        // not just its first.
+        let mut proposed_occs = Vec::new();
+        for pe in &proposal.events {
+            proposed_occs.append(&mut expand_proposed_event(pe, range_start, range_end)?);
+        }
+
         let existing = self.occurrences_in_range(range_start, range_end, calendar_name)?;
 
         let conflicts = detect_conflicts(&proposed_occs, &existing, check_internal);
@@ -285,24 +1009,41 @@ impl CalendarStore {
         for pe in proposal.events {
             let event = Event {
                 id: EventId::new(),
+                uid: None,
                 title: pe.title,
                 start: pe.start,
                 end: pe.end,
                 timezone: pe.timezone,
                 recurrence: pe.recurrence,
+                attendees: Vec::new(),
                 metadata: pe.metadata,
+                overrides: HashMap::new(),
             };
             ids.push(cal.add_event(event));
         }
 
+        for id in &ids {
+            self.record_change(ChangeKind::Added, *id, calendar_name);
+        }
+
         Ok(ids)
     }
 }
 
-/// Find the min start and max end across all proposed events.
+/// How far past a proposal's latest explicit event we look for conflicts when any
+/// event in the proposal recurs. Keeps the query window bounded rather than
+/// exploring an unbounded RRULE indefinitely.
+const RECURRING_CONFLICT_HORIZON_DAYS: i64 = 365;
+
+/// Find the min start and max end across all proposed events. If any event
+/// recurs, the end is pushed out by `RECURRING_CONFLICT_HORIZON_DAYS` so later
+/// occurrences are still checked for conflicts.
 fn proposed_time_bounds(events: &[ProposedEvent]) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
-    let start = events.iter().map(|e| e.start).min()?;
-    let end = events.iter().map(|e| e.end).max()?;
+    let start = events.iter().map(|e| e.start_utc()).min()?;
+    let mut end = events.iter().map(|e| e.end_utc()).max()?;
+    if events.iter().any(|e| e.recurrence.is_some()) {
+        end += TimeDelta::days(RECURRING_CONFLICT_HORIZON_DAYS);
+    }
     Some((start, end))
 }
 
@@ -318,12 +1059,15 @@ mod tests {
     fn make_event(title: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Event {
         Event {
             id: EventId::new(),
+            uid: None,
             title: title.to_string(),
-            start,
-            end,
+            start: event::EventTime::DateTime(start),
+            end: event::EventTime::DateTime(end),
             timezone: "UTC".to_string(),
             recurrence: None,
+            attendees: Vec::new(),
             metadata: Default::default(),
+            overrides: HashMap::new(),
         }
     }
 
@@ -368,9 +1112,104 @@ mod tests {
     }
 
     #[test]
-    fn store_default_calendar_exists() {
-        let store = CalendarStore::new();
-        assert!(store.get_calendar("default").is_some());
+    fn replace_events_discards_previous_set() {
+        let mut cal = Calendar::new("test".to_string());
+        cal.add_event(make_event("Old", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)));
+        cal.replace_events(vec![make_event("New", utc(2025, 1, 1, 14), utc(2025, 1, 1, 15))]);
+
+        let occs = cal
+            .occurrences_in_range(utc(2025, 1, 1, 0), utc(2025, 1, 2, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 1);
+        assert_eq!(occs[0].title, "New");
+    }
+
+    #[test]
+    fn upsert_event_by_google_id_replaces_existing() {
+        let mut cal = Calendar::new("test".to_string());
+        let mut first = make_event("Standup", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10));
+        first.metadata.insert("google_calendar_id".to_string(), "gcal1".to_string());
+        cal.add_event(first);
+
+        let mut updated = make_event("Standup (moved)", utc(2025, 1, 1, 11), utc(2025, 1, 1, 12));
+        updated.metadata.insert("google_calendar_id".to_string(), "gcal1".to_string());
+        cal.upsert_event_by_google_id(updated);
+
+        let occs = cal
+            .occurrences_in_range(utc(2025, 1, 1, 0), utc(2025, 1, 2, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 1);
+        assert_eq!(occs[0].title, "Standup (moved)");
+    }
+
+    #[test]
+    fn remove_events_by_google_id_applies_cancellations() {
+        let mut cal = Calendar::new("test".to_string());
+        let mut gone = make_event("Cancelled", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10));
+        gone.metadata.insert("google_calendar_id".to_string(), "gcal-gone".to_string());
+        cal.add_event(gone);
+        cal.add_event(make_event("Kept", utc(2025, 1, 1, 14), utc(2025, 1, 1, 15)));
+
+        let removed = cal.remove_events_by_google_id(&["gcal-gone".to_string()]);
+        assert_eq!(removed, 1);
+
+        let occs = cal
+            .occurrences_in_range(utc(2025, 1, 1, 0), utc(2025, 1, 2, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 1);
+        assert_eq!(occs[0].title, "Kept");
+    }
+
+    #[test]
+    fn google_sync_state_roundtrips() {
+        let mut cal = Calendar::new("test".to_string());
+        assert!(cal.google_sync_state().is_none());
+        cal.set_google_sync_state(GoogleSyncState {
+            calendar_id: "primary".to_string(),
+            sync_token: Some("token-1".to_string()),
+        });
+        let state = cal.google_sync_state().unwrap();
+        assert_eq!(state.calendar_id, "primary");
+        assert_eq!(state.sync_token.as_deref(), Some("token-1"));
+    }
+
+    #[test]
+    fn caldav_publish_state_roundtrips() {
+        let mut cal = Calendar::new("test".to_string());
+        assert!(cal.caldav_publish_state().is_none());
+
+        let mut etags = HashMap::new();
+        let event_id = EventId::new();
+        etags.insert(event_id, "\"etag-1\"".to_string());
+        cal.set_caldav_publish_state(CaldavPublishState {
+            collection_url: "https://caldav.example.com/cal".to_string(),
+            last_seq: 3,
+            etags,
+        });
+
+        let state = cal.caldav_publish_state().unwrap();
+        assert_eq!(state.collection_url, "https://caldav.example.com/cal");
+        assert_eq!(state.last_seq, 3);
+        assert_eq!(state.etags.get(&event_id).map(String::as_str), Some("\"etag-1\""));
+    }
+
+    #[test]
+    fn sync_state_detects_unchanged_body() {
+        let state = SyncState::new("https://example.com/cal.ics".to_string(), Some("\"abc\"".to_string()), None, "BODY");
+        assert!(state.body_unchanged("BODY"));
+        assert!(!state.body_unchanged("BODY CHANGED"));
+    }
+
+    #[test]
+    fn calendar_starts_with_no_subscription() {
+        let cal = Calendar::new("test".to_string());
+        assert!(cal.sync_state().is_none());
+    }
+
+    #[test]
+    fn store_default_calendar_exists() {
+        let store = CalendarStore::new();
+        assert!(store.get_calendar("default").is_some());
     }
 
     #[test]
@@ -394,8 +1233,8 @@ mod tests {
             "Option A".to_string(),
             vec![ProposedEvent {
                 title: "New Meeting".to_string(),
-                start: utc(2025, 1, 1, 14),
-                end: utc(2025, 1, 1, 15),
+                start: event::EventTime::DateTime(utc(2025, 1, 1, 14)),
+                end: event::EventTime::DateTime(utc(2025, 1, 1, 15)),
                 timezone: "UTC".to_string(),
                 recurrence: None,
                 metadata: Default::default(),
@@ -429,7 +1268,7 @@ mod tests {
         cal.add_event(make_event("Meeting", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)));
 
         // Find 30-min slots in 8-12 range
-        let slots = store
+        let (slots, truncated) = store
             .find_available_slots(
                 utc(2025, 1, 1, 8),
                 utc(2025, 1, 1, 12),
@@ -437,6 +1276,7 @@ mod tests {
                 Some("default"),
             )
             .unwrap();
+        assert!(!truncated);
         assert_eq!(slots.len(), 2);
         // 8:00-9:00 (1 hour) and 10:00-12:00 (2 hours)
         assert_eq!(slots[0].start, utc(2025, 1, 1, 8));
@@ -445,6 +1285,251 @@ mod tests {
         assert_eq!(slots[1].end, utc(2025, 1, 1, 12));
     }
 
+    #[test]
+    fn find_available_slots_within_hours_restricts_to_working_windows() {
+        use chrono::{NaiveTime, Weekday};
+
+        let mut store = CalendarStore::new();
+        let cal = store.get_or_create_calendar("default");
+        cal.add_event(make_event("Meeting", utc(2025, 1, 6, 10), utc(2025, 1, 6, 11)));
+
+        let mut availability = WeeklyAvailability::new(chrono_tz::UTC);
+        availability.add_window(
+            Weekday::Mon,
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        );
+
+        // 2025-01-06 is a Monday.
+        let (slots, truncated) = store
+            .find_available_slots_within_hours(
+                utc(2025, 1, 6, 0),
+                utc(2025, 1, 7, 0),
+                TimeDelta::minutes(30),
+                Some("default"),
+                &availability,
+            )
+            .unwrap();
+        assert!(!truncated);
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].start, utc(2025, 1, 6, 9));
+        assert_eq!(slots[0].end, utc(2025, 1, 6, 10));
+        assert_eq!(slots[1].start, utc(2025, 1, 6, 11));
+        assert_eq!(slots[1].end, utc(2025, 1, 6, 17));
+    }
+
+    #[test]
+    fn find_available_slots_with_travel_uses_registered_times() {
+        let mut store = CalendarStore::new();
+        store.set_travel_time("Office", "Gym", 20);
+        store.set_travel_time("Gym", "Airport", 40);
+
+        let cal = store.get_or_create_calendar("default");
+        let mut office_meeting = make_event("Standup", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10));
+        office_meeting.metadata.insert("location".to_string(), "Office".to_string());
+        cal.add_event(office_meeting);
+        let mut flight = make_event("Flight", utc(2025, 1, 1, 14), utc(2025, 1, 1, 15));
+        flight.metadata.insert("location".to_string(), "Airport".to_string());
+        cal.add_event(flight);
+
+        let (slots, truncated) = store
+            .find_available_slots_with_travel(
+                utc(2025, 1, 1, 8),
+                utc(2025, 1, 1, 17),
+                TimeDelta::minutes(30),
+                Some("default"),
+                Some("Gym"),
+                TimeDelta::minutes(15),
+            )
+            .unwrap();
+        assert!(!truncated);
+
+        let middle = slots
+            .iter()
+            .find(|s| s.preceding_location.as_deref() == Some("Office"))
+            .expect("gap between Office and Airport events");
+        assert_eq!(middle.leading_buffer_minutes, 20);
+        assert_eq!(middle.trailing_buffer_minutes, 40);
+        assert_eq!(middle.range.start, utc(2025, 1, 1, 10) + TimeDelta::minutes(20));
+        assert_eq!(middle.range.end, utc(2025, 1, 1, 14) - TimeDelta::minutes(40));
+    }
+
+    #[test]
+    fn free_busy_reports_truncation_from_an_unbounded_recurrence() {
+        let mut store = CalendarStore::new();
+        let cal = store.get_or_create_calendar("default");
+        cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 10),
+            "UTC",
+            "FREQ=DAILY",
+        ));
+
+        let (_, truncated) = store
+            .free_busy(utc(2025, 1, 1, 0), utc(2100, 1, 1, 0), Some("default"), false)
+            .unwrap();
+        assert!(truncated);
+    }
+
+    #[test]
+    fn find_mutual_free_slots_across_calendars() {
+        let mut store = CalendarStore::new();
+        store
+            .get_or_create_calendar("alice")
+            .add_event(make_event("Standup", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)));
+        store
+            .get_or_create_calendar("bob")
+            .add_event(make_event("Dentist", utc(2025, 1, 1, 14), utc(2025, 1, 1, 15)));
+
+        let (slots, truncated) = store
+            .find_mutual_free_slots(
+                utc(2025, 1, 1, 8),
+                utc(2025, 1, 1, 17),
+                TimeDelta::minutes(30),
+                &["alice".to_string(), "bob".to_string()],
+            )
+            .unwrap();
+        assert!(!truncated);
+        assert_eq!(slots.len(), 3);
+        assert_eq!(slots[0], TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 9)));
+        assert_eq!(slots[1], TimeRange::new(utc(2025, 1, 1, 10), utc(2025, 1, 1, 14)));
+        assert_eq!(slots[2], TimeRange::new(utc(2025, 1, 1, 15), utc(2025, 1, 1, 17)));
+    }
+
+    #[test]
+    fn find_mutual_free_slots_with_counts_reports_partial_availability() {
+        let mut store = CalendarStore::new();
+        store
+            .get_or_create_calendar("alice")
+            .add_event(make_event("Standup", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)));
+        store.get_or_create_calendar("bob");
+
+        let (slots, truncated) = store
+            .find_mutual_free_slots_with_counts(
+                utc(2025, 1, 1, 8),
+                utc(2025, 1, 1, 11),
+                TimeDelta::minutes(30),
+                &["alice".to_string(), "bob".to_string()],
+            )
+            .unwrap();
+        assert!(!truncated);
+        assert_eq!(
+            slots
+                .iter()
+                .map(|s| (s.range, s.available_participants))
+                .collect::<Vec<_>>(),
+            vec![
+                (TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 9)), 2),
+                (TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)), 1),
+                (TimeRange::new(utc(2025, 1, 1, 10), utc(2025, 1, 1, 11)), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_mutual_free_slots_errors_on_unknown_calendar() {
+        let store = CalendarStore::new();
+        let result = store.find_mutual_free_slots(
+            utc(2025, 1, 1, 8),
+            utc(2025, 1, 1, 17),
+            TimeDelta::minutes(30),
+            &["nonexistent".to_string()],
+        );
+        assert!(matches!(result, Err(TempoError::CalendarNotFound(_))));
+    }
+
+    #[test]
+    fn find_mutual_free_slots_reports_truncation_from_an_unbounded_recurrence() {
+        let mut store = CalendarStore::new();
+        store
+            .get_or_create_calendar("alice")
+            .add_event(make_recurring_event_with_tz(
+                "Standup",
+                utc(2025, 1, 1, 9),
+                utc(2025, 1, 1, 10),
+                "UTC",
+                "FREQ=DAILY",
+            ));
+        store.get_or_create_calendar("bob");
+
+        let (_, truncated) = store
+            .find_mutual_free_slots(
+                utc(2025, 1, 1, 0),
+                utc(2100, 1, 1, 0),
+                TimeDelta::minutes(30),
+                &["alice".to_string(), "bob".to_string()],
+            )
+            .unwrap();
+        assert!(truncated);
+    }
+
+    #[test]
+    fn get_changes_reports_additions_and_removals() {
+        let mut store = CalendarStore::new();
+        let id1 = store.add_event("default", make_event("Standup", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)));
+        store.add_event("default", make_event("Lunch", utc(2025, 1, 1, 12), utc(2025, 1, 1, 13)));
+
+        let (changes, token) = store.get_changes(0);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|c| c.kind == ChangeKind::Added));
+
+        store.remove_event("default", &id1).unwrap();
+
+        let (changes, token2) = store.get_changes(token);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, ChangeKind::Removed);
+        assert_eq!(changes[0].event_id, id1);
+
+        // A fresh call against the latest token sees nothing new.
+        let (changes, _) = store.get_changes(token2);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn get_changes_collapses_add_then_remove_within_window() {
+        let mut store = CalendarStore::new();
+        let (_, token) = store.get_changes(0);
+
+        let id = store.add_event("default", make_event("Temp", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)));
+        store.remove_event("default", &id).unwrap();
+
+        let (changes, _) = store.get_changes(token);
+        assert!(changes.is_empty(), "add-then-remove within the window should net to nothing");
+    }
+
+    #[test]
+    fn get_changes_records_clear_calendar_and_commit_proposal() {
+        let mut store = CalendarStore::new();
+        store.add_event("default", make_event("Keep", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)));
+        let (_, token) = store.get_changes(0);
+
+        let cleared = store.clear_calendar("default").unwrap();
+        assert_eq!(cleared, 1);
+
+        let proposal_id = store.create_proposal(
+            "Proposal".to_string(),
+            vec![crate::calendar::proposal::ProposedEvent {
+                title: "New".to_string(),
+                start: event::EventTime::DateTime(utc(2025, 1, 2, 9)),
+                end: event::EventTime::DateTime(utc(2025, 1, 2, 10)),
+                timezone: "UTC".to_string(),
+                recurrence: None,
+                metadata: Default::default(),
+            }],
+        );
+        let committed_ids = store.commit_proposal(&proposal_id, "default").unwrap();
+
+        let (changes, _) = store.get_changes(token);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().any(|c| c.kind == ChangeKind::Removed));
+        assert!(
+            changes
+                .iter()
+                .any(|c| c.kind == ChangeKind::Added && c.event_id == committed_ids[0])
+        );
+    }
+
     #[test]
     fn proposal_not_found_returns_error() {
         let store = CalendarStore::new();
@@ -466,8 +1551,8 @@ mod tests {
             "Conflicting".to_string(),
             vec![ProposedEvent {
                 title: "Overlap".to_string(),
-                start: utc(2025, 1, 1, 9),
-                end: utc(2025, 1, 1, 11),
+                start: event::EventTime::DateTime(utc(2025, 1, 1, 9)),
+                end: event::EventTime::DateTime(utc(2025, 1, 1, 11)),
                 timezone: "UTC".to_string(),
                 recurrence: None,
                 metadata: Default::default(),
@@ -479,4 +1564,415 @@ mod tests {
         assert_eq!(report.conflicts.len(), 1);
         assert_eq!(report.conflicts[0].overlap_minutes, 60);
     }
+
+    #[test]
+    fn store_proposal_detects_conflicts_on_recurring_instance() {
+        let mut store = CalendarStore::new();
+
+        // An existing event on the *third* week only — not the proposed
+        // recurrence's first occurrence.
+        let cal = store.get_or_create_calendar("default");
+        cal.add_event(make_event(
+            "Existing",
+            utc(2025, 1, 15, 9),
+            utc(2025, 1, 15, 10),
+        ));
+
+        let proposal_id = store.create_proposal(
+            "Weekly".to_string(),
+            vec![ProposedEvent {
+                title: "Weekly Sync".to_string(),
+                start: event::EventTime::DateTime(utc(2025, 1, 1, 9)),
+                end: event::EventTime::DateTime(utc(2025, 1, 1, 10)),
+                timezone: "UTC".to_string(),
+                recurrence: Some(event::RecurrenceRule {
+                    rrule: "FREQ=WEEKLY;COUNT=5".to_string(),
+                    exdates: Vec::new(),
+                    rdates: Vec::new(),
+                }),
+                metadata: Default::default(),
+            }],
+        );
+
+        let report = store.check_conflicts(&proposal_id, None, true).unwrap();
+        assert!(report.has_conflicts);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].proposed_start, utc(2025, 1, 15, 9));
+    }
+
+    #[test]
+    fn all_day_event_blocks_entire_day() {
+        let mut cal = Calendar::new("test".to_string());
+        cal.add_event(Event {
+            id: EventId::new(),
+            uid: None,
+            title: "Holiday".to_string(),
+            start: event::EventTime::Date(chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()),
+            end: event::EventTime::Date(chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()),
+            timezone: "UTC".to_string(),
+            recurrence: None,
+            attendees: Vec::new(),
+            metadata: Default::default(),
+            overrides: HashMap::new(),
+        });
+
+        let occs = cal
+            .occurrences_in_range(utc(2025, 1, 1, 8), utc(2025, 1, 1, 9))
+            .unwrap();
+        assert_eq!(occs.len(), 1);
+        assert!(occs[0].is_all_day);
+        assert_eq!(occs[0].start, utc(2025, 1, 1, 0));
+        assert_eq!(occs[0].end, utc(2025, 1, 2, 0));
+    }
+
+    fn make_recurring_event_with_tz(
+        title: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        timezone: &str,
+        rrule: &str,
+    ) -> Event {
+        Event {
+            id: EventId::new(),
+            uid: None,
+            title: title.to_string(),
+            start: event::EventTime::DateTime(start),
+            end: event::EventTime::DateTime(end),
+            timezone: timezone.to_string(),
+            recurrence: Some(RecurrenceRule {
+                rrule: rrule.to_string(),
+                exdates: Vec::new(),
+                rdates: Vec::new(),
+            }),
+            attendees: Vec::new(),
+            metadata: Default::default(),
+            overrides: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn weekly_recurrence_preserves_local_time_across_spring_forward() {
+        // US DST started 2025-03-09. A 9am America/New_York weekly meeting
+        // should stay at 9am local every week, even though that means the
+        // UTC instant shifts by an hour once the clocks change.
+        let mut cal = Calendar::new("test".to_string());
+        cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 3, 2, 14), // 9am EST = 14:00 UTC
+            utc(2025, 3, 2, 14), // zero-duration for simplicity
+            "America/New_York",
+            "FREQ=WEEKLY;COUNT=4",
+        ));
+
+        let occs = cal
+            .occurrences_in_range(utc(2025, 3, 1, 0), utc(2025, 4, 1, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 4);
+
+        let ny = chrono_tz::America::New_York;
+        for occ in &occs {
+            assert_eq!(occ.start.with_timezone(&ny).format("%H:%M").to_string(), "09:00");
+        }
+
+        // Before the spring-forward: EST is UTC-5.
+        assert_eq!(occs[0].start, utc(2025, 3, 2, 14));
+        // After it: EDT is UTC-4, so the UTC instant moved an hour earlier
+        // relative to a naive fixed 7-day step.
+        assert_eq!(occs[1].start, utc(2025, 3, 9, 13));
+        assert_eq!(occs[2].start, utc(2025, 3, 16, 13));
+    }
+
+    #[test]
+    fn weekly_recurrence_preserves_local_time_across_fall_back() {
+        // US DST ended 2025-11-02.
+        let mut cal = Calendar::new("test".to_string());
+        cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 10, 26, 13), // 9am EDT = 13:00 UTC
+            utc(2025, 10, 26, 13),
+            "America/New_York",
+            "FREQ=WEEKLY;COUNT=3",
+        ));
+
+        let occs = cal
+            .occurrences_in_range(utc(2025, 10, 20, 0), utc(2025, 11, 16, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 3);
+
+        let ny = chrono_tz::America::New_York;
+        for occ in &occs {
+            assert_eq!(occ.start.with_timezone(&ny).format("%H:%M").to_string(), "09:00");
+        }
+
+        // Before the fall-back: EDT is UTC-4.
+        assert_eq!(occs[0].start, utc(2025, 10, 26, 13));
+        // After it: EST is UTC-5.
+        assert_eq!(occs[1].start, utc(2025, 11, 2, 14));
+        assert_eq!(occs[2].start, utc(2025, 11, 9, 14));
+    }
+
+    #[test]
+    fn daily_recurrence_preserves_local_time_across_spring_forward() {
+        // The motivating case: a daily 9am America/New_York standup must stay
+        // at 9am local through the spring-forward boundary, not drift by an
+        // hour in UTC.
+        let mut cal = Calendar::new("test".to_string());
+        cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 3, 8, 14), // 9am EST = 14:00 UTC
+            utc(2025, 3, 8, 14),
+            "America/New_York",
+            "FREQ=DAILY;COUNT=3",
+        ));
+
+        let occs = cal
+            .occurrences_in_range(utc(2025, 3, 7, 0), utc(2025, 3, 11, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 3);
+
+        let ny = chrono_tz::America::New_York;
+        for occ in &occs {
+            assert_eq!(occ.start.with_timezone(&ny).format("%H:%M").to_string(), "09:00");
+        }
+
+        // 2025-03-08: still EST (UTC-5).
+        assert_eq!(occs[0].start, utc(2025, 3, 8, 14));
+        // 2025-03-09: clocks spring forward overnight, now EDT (UTC-4).
+        assert_eq!(occs[1].start, utc(2025, 3, 9, 13));
+        assert_eq!(occs[2].start, utc(2025, 3, 10, 13));
+    }
+
+    #[test]
+    fn rdate_adds_an_extra_occurrence_to_the_series() {
+        let mut cal = Calendar::new("test".to_string());
+        cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 10),
+            "UTC",
+            "FREQ=DAILY;COUNT=3",
+        ));
+        let event_id = cal.events().next().unwrap().id;
+        cal.events
+            .get_mut(&event_id)
+            .unwrap()
+            .recurrence
+            .as_mut()
+            .unwrap()
+            .rdates = vec![utc(2025, 1, 10, 9)];
+
+        let occs = cal
+            .occurrences_in_range(utc(2025, 1, 1, 0), utc(2025, 1, 15, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 4);
+        assert!(occs.iter().any(|o| o.start == utc(2025, 1, 10, 9)));
+    }
+
+    #[test]
+    fn cancel_occurrence_drops_it_from_expansion() {
+        let mut cal = Calendar::new("test".to_string());
+        let event_id = cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 10),
+            "UTC",
+            "FREQ=DAILY;COUNT=3",
+        ));
+
+        cal.cancel_occurrence(event_id, utc(2025, 1, 2, 9)).unwrap();
+
+        let occs = cal
+            .occurrences_in_range(utc(2025, 1, 1, 0), utc(2025, 1, 10, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 2);
+        assert!(!occs.iter().any(|o| o.start == utc(2025, 1, 2, 9)));
+    }
+
+    #[test]
+    fn detach_occurrence_overrides_fields_without_affecting_the_rest() {
+        let mut cal = Calendar::new("test".to_string());
+        let event_id = cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 10),
+            "UTC",
+            "FREQ=DAILY;COUNT=3",
+        ));
+
+        cal.detach_occurrence(
+            event_id,
+            utc(2025, 1, 2, 9),
+            event::RecurrenceOverride {
+                title: "Standup (moved)".to_string(),
+                start: event::EventTime::DateTime(utc(2025, 1, 2, 11)),
+                end: event::EventTime::DateTime(utc(2025, 1, 2, 12)),
+                metadata: Default::default(),
+                cancelled: false,
+            },
+        )
+        .unwrap();
+
+        let occs = cal
+            .occurrences_in_range(utc(2025, 1, 1, 0), utc(2025, 1, 10, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 3);
+        let moved = occs.iter().find(|o| o.start == utc(2025, 1, 2, 11)).expect("moved occurrence");
+        assert_eq!(moved.title, "Standup (moved)");
+    }
+
+    #[test]
+    fn cancel_occurrence_on_non_recurring_event_errors() {
+        let mut cal = Calendar::new("test".to_string());
+        let event_id = cal.add_event(make_event("Solo", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)));
+        assert!(cal.cancel_occurrence(event_id, utc(2025, 1, 1, 9)).is_err());
+    }
+
+    #[test]
+    fn invalid_timezone_is_rejected() {
+        let mut cal = Calendar::new("test".to_string());
+        cal.add_event(make_recurring_event_with_tz(
+            "Bad TZ",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 9),
+            "Not/A_Zone",
+            "FREQ=WEEKLY;COUNT=2",
+        ));
+
+        let result = cal.occurrences_in_range(utc(2025, 1, 1, 0), utc(2025, 2, 1, 0));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn occurrences_iter_merges_events_in_chronological_order() {
+        let mut cal = Calendar::new("test".to_string());
+        cal.add_event(make_event("Afternoon", utc(2025, 1, 1, 14), utc(2025, 1, 1, 15)));
+        cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 10),
+            "UTC",
+            "FREQ=DAILY;COUNT=3",
+        ));
+        cal.add_event(make_event("Lunch", utc(2025, 1, 1, 12), utc(2025, 1, 1, 13)));
+
+        let occs: Vec<_> = cal
+            .occurrences_iter(utc(2025, 1, 1, 0), utc(2025, 1, 4, 0))
+            .unwrap()
+            .collect();
+        let starts: Vec<_> = occs.iter().map(|o| o.start).collect();
+        let mut sorted = starts.clone();
+        sorted.sort();
+        assert_eq!(starts, sorted);
+        assert_eq!(starts.len(), 5); // 1 afternoon + 3 standups + 1 lunch
+    }
+
+    #[test]
+    fn occurrences_in_range_truncated_reports_no_truncation_under_the_bound() {
+        let mut cal = Calendar::new("test".to_string());
+        cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 10),
+            "UTC",
+            "FREQ=DAILY;COUNT=5",
+        ));
+
+        let (occs, truncated) = cal
+            .occurrences_in_range_truncated(utc(2025, 1, 1, 0), utc(2025, 1, 10, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 5);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn occurrences_in_range_truncated_detects_a_recurrence_past_the_safety_bound() {
+        let mut cal = Calendar::new("test".to_string());
+        // An unbounded daily recurrence queried across centuries will hit
+        // MAX_RECURRENCE_OCCURRENCES (1000) long before range_end.
+        cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 10),
+            "UTC",
+            "FREQ=DAILY",
+        ));
+
+        let (_, truncated) = cal
+            .occurrences_in_range_truncated(utc(2025, 1, 1, 0), utc(2100, 1, 1, 0))
+            .unwrap();
+        assert!(truncated);
+    }
+
+    #[test]
+    fn fixed_and_repeating_events_are_partitioned() {
+        let mut cal = Calendar::new("test".to_string());
+        cal.add_event(make_event("Lunch", utc(2025, 1, 1, 12), utc(2025, 1, 1, 13)));
+        cal.add_event(make_recurring_event_with_tz(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 10),
+            "UTC",
+            "FREQ=DAILY;COUNT=3",
+        ));
+
+        assert_eq!(cal.fixed_events().count(), 1);
+        assert_eq!(cal.fixed_events().next().unwrap().title, "Lunch");
+        assert_eq!(cal.repeating_events().count(), 1);
+        assert_eq!(cal.repeating_events().next().unwrap().title, "Standup");
+    }
+
+    #[test]
+    fn get_by_uid_finds_the_matching_event() {
+        let mut cal = Calendar::new("test".to_string());
+        let mut event = make_event("Standup", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10));
+        event.uid = Some("external-1".to_string());
+        cal.add_event(event);
+
+        assert_eq!(cal.get_by_uid("external-1").map(|e| e.title.as_str()), Some("Standup"));
+        assert!(cal.get_by_uid("no-such-uid").is_none());
+    }
+
+    #[test]
+    fn upsert_by_uid_replaces_the_existing_event_instead_of_duplicating() {
+        let mut cal = Calendar::new("test".to_string());
+        let mut first = make_event("Standup", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10));
+        first.uid = Some("series-1".to_string());
+        cal.add_event(first);
+
+        let mut updated = make_event("Standup (moved)", utc(2025, 1, 1, 11), utc(2025, 1, 1, 12));
+        updated.uid = Some("series-1".to_string());
+        cal.upsert_by_uid(updated);
+
+        assert_eq!(cal.events().count(), 1);
+        let occs = cal
+            .occurrences_in_range(utc(2025, 1, 1, 0), utc(2025, 1, 2, 0))
+            .unwrap();
+        assert_eq!(occs.len(), 1);
+        assert_eq!(occs[0].title, "Standup (moved)");
+    }
+
+    #[test]
+    fn upsert_by_uid_with_no_prior_match_just_adds() {
+        let mut cal = Calendar::new("test".to_string());
+        let mut event = make_event("Standup", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10));
+        event.uid = Some("series-1".to_string());
+        cal.upsert_by_uid(event);
+
+        assert_eq!(cal.events().count(), 1);
+        assert!(cal.get_by_uid("series-1").is_some());
+    }
+
+    #[test]
+    fn remove_event_clears_it_from_the_uid_index_and_partition() {
+        let mut cal = Calendar::new("test".to_string());
+        let mut event = make_event("Standup", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10));
+        event.uid = Some("series-1".to_string());
+        let id = event.id;
+        cal.add_event(event);
+
+        cal.remove_event(&id);
+
+        assert!(cal.get_by_uid("series-1").is_none());
+        assert_eq!(cal.fixed_events().count(), 0);
+    }
 }
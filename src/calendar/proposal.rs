@@ -4,7 +4,9 @@ use std::collections::HashMap;
 use std::fmt;
 use uuid::Uuid;
 
-use super::event::{EventId, EventOccurrence, RecurrenceRule};
+use crate::error::TempoError;
+
+use super::event::{EventId, EventOccurrence, EventTime, RecurrenceRule, Transparency, parse_iana_tz};
 use super::time_utils::TimeRange;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
@@ -25,13 +27,29 @@ impl fmt::Display for ProposalId {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProposedEvent {
     pub title: String,
-    pub start: DateTime<Utc>,
-    pub end: DateTime<Utc>,
+    pub start: EventTime,
+    pub end: EventTime,
     pub timezone: String,
     pub recurrence: Option<RecurrenceRule>,
     pub metadata: HashMap<String, String>,
 }
 
+impl ProposedEvent {
+    pub fn start_utc(&self) -> DateTime<Utc> {
+        self.start.as_start_instant()
+    }
+
+    pub fn end_utc(&self) -> DateTime<Utc> {
+        self.end.as_end_instant()
+    }
+
+    /// Resolve the `timezone` field to a concrete IANA timezone, used to
+    /// anchor recurrence expansion in local wall-clock time.
+    pub fn timezone_tz(&self) -> Result<chrono_tz::Tz, TempoError> {
+        parse_iana_tz(&self.timezone)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Proposal {
     pub id: ProposalId,
@@ -132,6 +150,8 @@ mod tests {
             start,
             end,
             is_recurring: false,
+            is_all_day: false,
+            transparency: Transparency::Busy,
             metadata: Default::default(),
         }
     }
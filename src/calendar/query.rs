@@ -0,0 +1,220 @@
+//! CalDAV-style `calendar-query` REPORT over parsed `Event`s: combinable
+//! predicates for time-range overlap, text matching on SUMMARY/LOCATION/
+//! DESCRIPTION, and property existence, so MCP tools get one place to ask
+//! "what's on my calendar between X and Y that mentions Z" instead of each
+//! re-implementing overlap math.
+
+use chrono::{DateTime, Utc};
+
+use crate::error::TempoError;
+
+use super::event::Event;
+use super::recurrence;
+
+/// The event property a `TextMatch` or `PropertyExists` predicate targets -
+/// the subset of RFC 5545 VEVENT properties we expose for filtering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventProperty {
+    Summary,
+    Location,
+    Description,
+}
+
+impl EventProperty {
+    fn value(self, event: &Event) -> Option<&str> {
+        match self {
+            EventProperty::Summary => Some(event.title.as_str()),
+            EventProperty::Location => event.metadata.get("location").map(String::as_str),
+            EventProperty::Description => event.metadata.get("description").map(String::as_str),
+        }
+    }
+}
+
+/// A `calendar-query`-style predicate over an `Event`. `All` ANDs its
+/// sub-filters together, mirroring the nested `<C:comp-filter>` structure of
+/// a CalDAV REPORT.
+#[derive(Debug, Clone)]
+pub enum Filter {
+    /// Keep events whose interval overlaps `[start, end)`.
+    TimeRange { start: DateTime<Utc>, end: DateTime<Utc> },
+    /// Keep events where `property` contains `needle` as a case-insensitive substring.
+    TextMatch { property: EventProperty, needle: String },
+    /// Keep events where `property` is present and non-empty.
+    PropertyExists(EventProperty),
+    /// Keep events matching every sub-filter.
+    All(Vec<Filter>),
+}
+
+impl Filter {
+    fn matches(&self, event: &Event) -> bool {
+        match self {
+            Filter::TimeRange { start, end } => event.start_utc() < *end && *start < event.end_utc(),
+            Filter::TextMatch { property, needle } => property
+                .value(event)
+                .is_some_and(|v| v.to_lowercase().contains(&needle.to_lowercase())),
+            Filter::PropertyExists(property) => property.value(event).is_some_and(|v| !v.is_empty()),
+            Filter::All(filters) => filters.iter().all(|f| f.matches(event)),
+        }
+    }
+
+    /// The narrowest `[start, end)` window implied by any `TimeRange`
+    /// predicates nested in this filter, used to bound recurrence expansion.
+    /// `None` if the filter contains no `TimeRange` predicate.
+    fn time_range_bounds(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        match self {
+            Filter::TimeRange { start, end } => Some((*start, *end)),
+            Filter::All(filters) => filters
+                .iter()
+                .filter_map(|f| f.time_range_bounds())
+                .reduce(|(a_start, a_end), (b_start, b_end)| (a_start.max(b_start), a_end.min(b_end))),
+            _ => None,
+        }
+    }
+}
+
+/// Run a CalDAV-style `calendar-query` over `events`: expand any recurring
+/// event into its concrete occurrences within `filter`'s nested `TimeRange`
+/// bound (or `default_window` if `filter` has none), then keep the
+/// occurrences `filter` matches.
+pub fn filter_events(
+    events: &[Event],
+    filter: &Filter,
+    default_window: (DateTime<Utc>, DateTime<Utc>),
+) -> Result<Vec<Event>, TempoError> {
+    let (window_start, window_end) = filter.time_range_bounds().unwrap_or(default_window);
+
+    let mut matched = Vec::new();
+    for event in events {
+        for occurrence in recurrence::expand(event, window_start, window_end)? {
+            if filter.matches(&occurrence) {
+                matched.push(occurrence);
+            }
+        }
+    }
+    Ok(matched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::event::{EventId, EventTime, RecurrenceRule};
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    fn make_event(title: &str, start: DateTime<Utc>, end: DateTime<Utc>, metadata: HashMap<String, String>) -> Event {
+        Event {
+            id: EventId::new(),
+            uid: None,
+            title: title.to_string(),
+            start: EventTime::DateTime(start),
+            end: EventTime::DateTime(end),
+            timezone: "UTC".to_string(),
+            recurrence: None,
+            attendees: Vec::new(),
+            metadata,
+            overrides: HashMap::new(),
+        }
+    }
+
+    fn window() -> (DateTime<Utc>, DateTime<Utc>) {
+        (utc(2025, 1, 1, 0), utc(2025, 1, 2, 0))
+    }
+
+    #[test]
+    fn time_range_keeps_only_overlapping_events() {
+        let events = vec![
+            make_event("Morning", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10), Default::default()),
+            make_event("Evening", utc(2025, 1, 1, 20), utc(2025, 1, 1, 21), Default::default()),
+        ];
+        let filter = Filter::TimeRange { start: utc(2025, 1, 1, 8), end: utc(2025, 1, 1, 11) };
+
+        let matched = filter_events(&events, &filter, window()).unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].title, "Morning");
+    }
+
+    #[test]
+    fn text_match_is_case_insensitive_substring() {
+        let events = vec![
+            make_event("Dentist appointment", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10), Default::default()),
+            make_event("Lunch", utc(2025, 1, 1, 12), utc(2025, 1, 1, 13), Default::default()),
+        ];
+        let filter = Filter::TextMatch { property: EventProperty::Summary, needle: "DENTIST".to_string() };
+
+        let matched = filter_events(&events, &filter, window()).unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].title, "Dentist appointment");
+    }
+
+    #[test]
+    fn text_match_checks_location_and_description() {
+        let mut with_location = HashMap::new();
+        with_location.insert("location".to_string(), "Room 101".to_string());
+        let events = vec![
+            make_event("Standup", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10), with_location),
+            make_event("Standup", utc(2025, 1, 1, 11), utc(2025, 1, 1, 12), Default::default()),
+        ];
+        let filter = Filter::TextMatch { property: EventProperty::Location, needle: "101".to_string() };
+
+        let matched = filter_events(&events, &filter, window()).unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].start_utc(), utc(2025, 1, 1, 9));
+    }
+
+    #[test]
+    fn property_exists_requires_non_empty_value() {
+        let mut with_description = HashMap::new();
+        with_description.insert("description".to_string(), "Quarterly planning".to_string());
+        let events = vec![
+            make_event("Planning", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10), with_description),
+            make_event("No description", utc(2025, 1, 1, 11), utc(2025, 1, 1, 12), Default::default()),
+        ];
+        let filter = Filter::PropertyExists(EventProperty::Description);
+
+        let matched = filter_events(&events, &filter, window()).unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].title, "Planning");
+    }
+
+    #[test]
+    fn all_combines_predicates_with_and() {
+        let events = vec![
+            make_event("Team sync", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10), Default::default()),
+            make_event("Team sync", utc(2025, 1, 1, 20), utc(2025, 1, 1, 21), Default::default()),
+            make_event("Lunch", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10), Default::default()),
+        ];
+        let filter = Filter::All(vec![
+            Filter::TimeRange { start: utc(2025, 1, 1, 8), end: utc(2025, 1, 1, 11) },
+            Filter::TextMatch { property: EventProperty::Summary, needle: "sync".to_string() },
+        ]);
+
+        let matched = filter_events(&events, &filter, window()).unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].start_utc(), utc(2025, 1, 1, 9));
+    }
+
+    #[test]
+    fn recurring_event_is_expanded_before_matching() {
+        let event = Event {
+            id: EventId::new(),
+            uid: None,
+            title: "Standup".to_string(),
+            start: EventTime::DateTime(utc(2025, 1, 1, 9)),
+            end: EventTime::DateTime(utc(2025, 1, 1, 9)),
+            timezone: "UTC".to_string(),
+            recurrence: Some(RecurrenceRule { rrule: "FREQ=DAILY;COUNT=5".to_string(), exdates: Vec::new(), rdates: Vec::new() }),
+            attendees: Vec::new(),
+            metadata: Default::default(),
+            overrides: HashMap::new(),
+        };
+        let filter = Filter::TimeRange { start: utc(2025, 1, 2, 0), end: utc(2025, 1, 4, 0) };
+
+        let matched = filter_events(&[event], &filter, window()).unwrap();
+        assert_eq!(matched.len(), 2);
+        assert!(matched.iter().all(|e| e.recurrence.is_none()));
+    }
+}
@@ -0,0 +1,249 @@
+//! Materializes concrete per-occurrence `Event`s from a recurring `Event`'s
+//! RRULE, for callers that need individual instances to own (e.g. to hand
+//! off, mutate, or serialize one at a time) rather than the lighter-weight
+//! `EventOccurrence` view `expand_event` produces for conflict/agenda/slot
+//! queries.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::TempoError;
+
+use super::event::{Event, EventId, EventTime};
+use super::recurrence_starts_in_range;
+
+/// Expand `event`'s recurrence into concrete one-off `Event`s whose starts
+/// fall within `[window_start, window_end)`. Each occurrence keeps the
+/// parent's title/metadata/uid and duration, but gets its own start/end, a
+/// fresh `id`, and no `recurrence`. A non-recurring event is returned as a
+/// single clone of itself, unfiltered by the window.
+pub fn expand(
+    event: &Event,
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+) -> Result<Vec<Event>, TempoError> {
+    let Some(recurrence) = &event.recurrence else {
+        return Ok(vec![event.clone()]);
+    };
+
+    let tz = event.timezone_tz()?;
+    let start_utc = event.start_utc();
+    let duration = event.end_utc() - start_utc;
+    let is_all_day = event.is_all_day();
+    let (starts, _truncated) =
+        recurrence_starts_in_range(start_utc, tz, recurrence, window_start, window_end)?;
+
+    Ok(starts
+        .into_iter()
+        .filter_map(|occurrence_start| {
+            if let Some(over) = event.overrides.get(&occurrence_start) {
+                if over.cancelled {
+                    return None;
+                }
+                return Some(Event {
+                    id: EventId::new(),
+                    uid: event.uid.clone(),
+                    title: over.title.clone(),
+                    start: over.start,
+                    end: over.end,
+                    timezone: event.timezone.clone(),
+                    recurrence: None,
+                    attendees: event.attendees.clone(),
+                    metadata: over.metadata.clone(),
+                    overrides: HashMap::new(),
+                });
+            }
+            let end = occurrence_start + duration;
+            let (start, end) = if is_all_day {
+                (EventTime::Date(occurrence_start.date_naive()), EventTime::Date(end.date_naive()))
+            } else {
+                (EventTime::DateTime(occurrence_start), EventTime::DateTime(end))
+            };
+            Some(Event {
+                id: EventId::new(),
+                uid: event.uid.clone(),
+                title: event.title.clone(),
+                start,
+                end,
+                timezone: event.timezone.clone(),
+                recurrence: None,
+                attendees: event.attendees.clone(),
+                metadata: event.metadata.clone(),
+                overrides: HashMap::new(),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::event::RecurrenceRule;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    fn make_event(title: &str, start: DateTime<Utc>, end: DateTime<Utc>, rrule: Option<&str>) -> Event {
+        Event {
+            id: EventId::new(),
+            uid: None,
+            title: title.to_string(),
+            start: EventTime::DateTime(start),
+            end: EventTime::DateTime(end),
+            timezone: "UTC".to_string(),
+            recurrence: rrule.map(|r| RecurrenceRule { rrule: r.to_string(), exdates: Vec::new(), rdates: Vec::new() }),
+            attendees: Vec::new(),
+            metadata: Default::default(),
+            overrides: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn non_recurring_event_expands_to_itself() {
+        let event = make_event("Solo", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10), None);
+        let occurrences = expand(&event, utc(2025, 1, 1, 0), utc(2025, 1, 2, 0)).unwrap();
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].start_utc(), utc(2025, 1, 1, 9));
+        assert!(occurrences[0].recurrence.is_none());
+    }
+
+    #[test]
+    fn daily_count_expands_within_window() {
+        let event = make_event(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 9),
+            Some("FREQ=DAILY;COUNT=5"),
+        );
+        let occurrences = expand(&event, utc(2025, 1, 1, 0), utc(2025, 1, 10, 0)).unwrap();
+        assert_eq!(occurrences.len(), 5);
+        for occurrence in &occurrences {
+            assert_eq!(occurrence.title, "Standup");
+            assert!(occurrence.recurrence.is_none());
+        }
+        assert_eq!(occurrences[4].start_utc(), utc(2025, 1, 5, 9));
+    }
+
+    #[test]
+    fn window_bounds_an_unbounded_rule() {
+        let event = make_event(
+            "Weekly",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 10),
+            Some("FREQ=WEEKLY"),
+        );
+        let occurrences = expand(&event, utc(2025, 1, 1, 0), utc(2025, 2, 1, 0)).unwrap();
+        assert_eq!(occurrences.len(), 5);
+        assert_eq!(occurrences[0].end_utc() - occurrences[0].start_utc(), occurrences[4].end_utc() - occurrences[4].start_utc());
+    }
+
+    #[test]
+    fn all_day_recurrence_keeps_date_occurrences() {
+        let mut event = make_event(
+            "Holiday",
+            utc(2025, 1, 1, 0),
+            utc(2025, 1, 2, 0),
+            Some("FREQ=YEARLY;COUNT=2"),
+        );
+        event.start = EventTime::Date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+        event.end = EventTime::Date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+
+        let occurrences = expand(&event, utc(2025, 1, 1, 0), utc(2027, 1, 1, 0)).unwrap();
+        assert_eq!(occurrences.len(), 2);
+        for occurrence in &occurrences {
+            assert!(occurrence.is_all_day());
+        }
+    }
+
+    #[test]
+    fn exdate_drops_that_occurrence() {
+        let mut event = make_event(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 9),
+            Some("FREQ=DAILY;COUNT=5"),
+        );
+        event.recurrence.as_mut().unwrap().exdates = vec![utc(2025, 1, 3, 9)];
+
+        let occurrences = expand(&event, utc(2025, 1, 1, 0), utc(2025, 1, 10, 0)).unwrap();
+        let starts: Vec<_> = occurrences.iter().map(|e| e.start_utc()).collect();
+        assert_eq!(starts.len(), 4);
+        assert!(!starts.contains(&utc(2025, 1, 3, 9)));
+    }
+
+    #[test]
+    fn override_replaces_the_generated_occurrence() {
+        use crate::calendar::event::RecurrenceOverride;
+
+        let mut event = make_event(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 9),
+            Some("FREQ=DAILY;COUNT=3"),
+        );
+        event.overrides.insert(
+            utc(2025, 1, 2, 9),
+            RecurrenceOverride {
+                title: "Standup (moved)".to_string(),
+                start: EventTime::DateTime(utc(2025, 1, 2, 11)),
+                end: EventTime::DateTime(utc(2025, 1, 2, 11)),
+                metadata: Default::default(),
+                cancelled: false,
+            },
+        );
+
+        let occurrences = expand(&event, utc(2025, 1, 1, 0), utc(2025, 1, 10, 0)).unwrap();
+        assert_eq!(occurrences.len(), 3);
+        let moved = occurrences
+            .iter()
+            .find(|e| e.start_utc() == utc(2025, 1, 2, 11))
+            .expect("moved occurrence present");
+        assert_eq!(moved.title, "Standup (moved)");
+    }
+
+    #[test]
+    fn rdate_splices_in_an_extra_occurrence() {
+        let mut event = make_event(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 9),
+            Some("FREQ=DAILY;COUNT=3"),
+        );
+        event.recurrence.as_mut().unwrap().rdates = vec![utc(2025, 1, 10, 9)];
+
+        let occurrences = expand(&event, utc(2025, 1, 1, 0), utc(2025, 1, 15, 0)).unwrap();
+        let starts: Vec<_> = occurrences.iter().map(|e| e.start_utc()).collect();
+        assert_eq!(starts.len(), 4);
+        assert!(starts.contains(&utc(2025, 1, 10, 9)));
+    }
+
+    #[test]
+    fn cancelled_override_drops_that_occurrence() {
+        use crate::calendar::event::RecurrenceOverride;
+
+        let mut event = make_event(
+            "Standup",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 1, 9),
+            Some("FREQ=DAILY;COUNT=3"),
+        );
+        event.overrides.insert(
+            utc(2025, 1, 2, 9),
+            RecurrenceOverride {
+                title: String::new(),
+                start: EventTime::DateTime(utc(2025, 1, 2, 9)),
+                end: EventTime::DateTime(utc(2025, 1, 2, 9)),
+                metadata: Default::default(),
+                cancelled: true,
+            },
+        );
+
+        let occurrences = expand(&event, utc(2025, 1, 1, 0), utc(2025, 1, 10, 0)).unwrap();
+        let starts: Vec<_> = occurrences.iter().map(|e| e.start_utc()).collect();
+        assert_eq!(starts.len(), 2);
+        assert!(!starts.contains(&utc(2025, 1, 2, 9)));
+    }
+}
@@ -1,7 +1,12 @@
-use chrono::{DateTime, TimeDelta, Utc};
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
-use super::event::EventOccurrence;
+use crate::error::TempoError;
+
+use super::event::{EventOccurrence, Transparency};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct TimeRange {
@@ -39,11 +44,17 @@ impl TimeRange {
 pub struct BusyPeriod {
     pub range: TimeRange,
     pub event_titles: Vec<String>,
+    /// The classification of the busiest occurrence merged into this period
+    /// (`Busy` takes precedence over `OutOfOffice` when both overlap).
+    pub transparency: Transparency,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct FreeBusyResult {
     pub busy_periods: Vec<BusyPeriod>,
+    /// Tentative occurrences, reported separately rather than merged into
+    /// `busy_periods` so callers can distinguish hard conflicts from soft ones.
+    pub tentative_periods: Vec<BusyPeriod>,
     pub free_periods: Vec<TimeRange>,
     pub total_busy_minutes: i64,
     pub total_free_minutes: i64,
@@ -69,6 +80,104 @@ fn merge_ranges(ranges: &[TimeRange]) -> Vec<TimeRange> {
     merged
 }
 
+/// A normalized (sorted, non-overlapping) set of half-open `TimeRange`s,
+/// supporting the standard interval-set algebra. Generalizes the ad-hoc
+/// merge that `find_free_slots` used to do inline, so operations like
+/// "mutual busy time across attendees" (a union) or "mutual free time" (a
+/// complement of that union) have a composable core to build on instead of
+/// hand-rolled merge/subtract loops at each call site.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TimeRangeSet {
+    ranges: Vec<TimeRange>,
+}
+
+impl TimeRangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a set from arbitrary (possibly overlapping, unsorted) ranges,
+    /// normalizing them into sorted non-overlapping form.
+    pub fn from_ranges(ranges: &[TimeRange]) -> Self {
+        Self {
+            ranges: merge_ranges(ranges),
+        }
+    }
+
+    /// The normalized ranges, sorted and non-overlapping.
+    pub fn ranges(&self) -> &[TimeRange] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Time covered by `self` or `other` (or both).
+    pub fn union(&self, other: &TimeRangeSet) -> TimeRangeSet {
+        let mut combined = self.ranges.clone();
+        combined.extend(other.ranges.iter().copied());
+        TimeRangeSet::from_ranges(&combined)
+    }
+
+    /// Time covered by both `self` and `other`. Walks both sorted range
+    /// lists with two cursors, emitting `[max(a.start,b.start),
+    /// min(a.end,b.end))` whenever that's non-empty and advancing whichever
+    /// range ends first.
+    pub fn intersect(&self, other: &TimeRangeSet) -> TimeRangeSet {
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let a = self.ranges[i];
+            let b = other.ranges[j];
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                result.push(TimeRange::new(start, end));
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        TimeRangeSet { ranges: result }
+    }
+
+    /// Time covered by `self` but not by `other`: subtracts every range in
+    /// `other` from every range in `self`.
+    pub fn difference(&self, other: &TimeRangeSet) -> TimeRangeSet {
+        let mut result = Vec::new();
+        for a in &self.ranges {
+            let mut remaining = vec![*a];
+            for b in &other.ranges {
+                let mut next = Vec::new();
+                for r in remaining {
+                    if !r.overlaps(b) {
+                        next.push(r);
+                        continue;
+                    }
+                    if r.start < b.start {
+                        next.push(TimeRange::new(r.start, b.start));
+                    }
+                    if b.end < r.end {
+                        next.push(TimeRange::new(b.end, r.end));
+                    }
+                }
+                remaining = next;
+            }
+            result.extend(remaining);
+        }
+        result.sort_by_key(|r| r.start);
+        TimeRangeSet { ranges: result }
+    }
+
+    /// Time in `within` not covered by `self`.
+    pub fn complement(&self, within: TimeRange) -> TimeRangeSet {
+        TimeRangeSet::from_ranges(&[within]).difference(self)
+    }
+}
+
 /// Find free slots of at least `min_duration` within `search_range`,
 /// given a set of busy periods.
 pub fn find_free_slots(
@@ -76,12 +185,12 @@ pub fn find_free_slots(
     search_range: &TimeRange,
     min_duration: TimeDelta,
 ) -> Vec<TimeRange> {
-    let merged = merge_ranges(busy_periods);
+    let merged = TimeRangeSet::from_ranges(busy_periods);
 
     let mut free = Vec::new();
     let mut cursor = search_range.start;
 
-    for period in &merged {
+    for period in merged.ranges() {
         if period.start > cursor {
             let gap = TimeRange::new(cursor, period.start.min(search_range.end));
             if gap.duration() >= min_duration {
@@ -105,59 +214,467 @@ pub fn find_free_slots(
     free
 }
 
-/// Compute free/busy breakdown for a time range given event occurrences.
-pub fn compute_free_busy(
-    occurrences: &[EventOccurrence],
-    range: &TimeRange,
-) -> FreeBusyResult {
-    if occurrences.is_empty() {
-        return FreeBusyResult {
-            busy_periods: vec![],
-            free_periods: vec![*range],
-            total_busy_minutes: 0,
-            total_free_minutes: range.duration().num_minutes(),
-        };
+/// A daily open interval in local wall-clock time (e.g. 09:00-17:00).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DailyWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+/// Recurring per-weekday working hours in a given timezone, used by
+/// `find_free_slots_within_hours` to restrict candidate slots to known
+/// availability (e.g. 09:00-17:00 Mon-Fri) instead of a raw 24h search range.
+/// A weekday with no registered window (e.g. a weekend) has no availability
+/// at all that day.
+#[derive(Debug, Clone)]
+pub struct WeeklyAvailability {
+    tz: Tz,
+    windows: HashMap<Weekday, Vec<DailyWindow>>,
+}
+
+impl WeeklyAvailability {
+    pub fn new(tz: Tz) -> Self {
+        Self {
+            tz,
+            windows: HashMap::new(),
+        }
     }
 
-    // Collect all busy ranges clipped to the search range
-    let mut busy_ranges: Vec<(TimeRange, String)> = occurrences
-        .iter()
-        .filter_map(|occ| {
-            let occ_range = TimeRange::new(occ.start, occ.end);
-            if occ_range.overlaps(range) {
-                let clipped = TimeRange::new(
-                    occ.start.max(range.start),
-                    occ.end.min(range.end),
-                );
-                Some((clipped, occ.title.clone()))
-            } else {
-                None
+    /// Register an open interval on `weekday`, in local wall-clock time.
+    pub fn add_window(&mut self, weekday: Weekday, start: NaiveTime, end: NaiveTime) {
+        self.windows
+            .entry(weekday)
+            .or_default()
+            .push(DailyWindow { start, end });
+    }
+
+    /// Expand the registered per-weekday windows into concrete UTC ranges for
+    /// every local calendar day `search_range` spans, resolving each local
+    /// wall-clock time to a UTC instant in `tz` (so DST transitions shift the
+    /// window's UTC bounds correctly day to day). Errors if a registered
+    /// window's start or end falls in a DST spring-forward gap on one of
+    /// those days.
+    fn expand_to_utc(&self, search_range: &TimeRange) -> Result<Vec<TimeRange>, TempoError> {
+        let first_day = search_range.start.with_timezone(&self.tz).date_naive();
+        let last_day = (search_range.end - TimeDelta::nanoseconds(1))
+            .with_timezone(&self.tz)
+            .date_naive();
+
+        let mut ranges = Vec::new();
+        let mut day = first_day;
+        while day <= last_day {
+            if let Some(day_windows) = self.windows.get(&day.weekday()) {
+                for w in day_windows {
+                    let start = local_time_to_utc(day, w.start, self.tz)?;
+                    let end = local_time_to_utc(day, w.end, self.tz)?;
+                    if start < end {
+                        ranges.push(TimeRange::new(start, end));
+                    }
+                }
             }
-        })
+            day = day.succ_opt().unwrap();
+        }
+        Ok(ranges)
+    }
+}
+
+/// Resolve a local wall-clock `time` on `date` in `tz` to the UTC instant it
+/// represents, taking the earliest valid instant for an ambiguous (DST
+/// fall-back) local time, mirroring `local_midnight_utc`. Errors if `time`
+/// falls in a DST spring-forward gap on `date` (no valid instant exists).
+fn local_time_to_utc(date: NaiveDate, time: NaiveTime, tz: Tz) -> Result<DateTime<Utc>, TempoError> {
+    match tz.from_local_datetime(&date.and_time(time)) {
+        chrono::LocalResult::Single(dt) => Ok(dt.with_timezone(&Utc)),
+        chrono::LocalResult::Ambiguous(earlier, _later) => Ok(earlier.with_timezone(&Utc)),
+        chrono::LocalResult::None => Err(TempoError::InvalidTimeRange(format!(
+            "{} {} does not exist in {} (falls in a DST gap)",
+            date, time, tz
+        ))),
+    }
+}
+
+/// As `find_free_slots`, but additionally restricts candidate slots to
+/// `availability`'s recurring daily working windows (e.g. 09:00-17:00,
+/// Mon-Fri) instead of the raw `search_range`. Internally intersects the
+/// available windows (expanded to UTC across every day `search_range` spans)
+/// with the complement of `busy_periods`, then filters by `min_duration`.
+/// Days with no configured window (e.g. weekends) contribute no candidate
+/// slots.
+pub fn find_free_slots_within_hours(
+    busy_periods: &[TimeRange],
+    search_range: &TimeRange,
+    min_duration: TimeDelta,
+    availability: &WeeklyAvailability,
+) -> Result<Vec<TimeRange>, TempoError> {
+    let working_hours = TimeRangeSet::from_ranges(&availability.expand_to_utc(search_range)?)
+        .intersect(&TimeRangeSet::from_ranges(&[*search_range]));
+    let busy = TimeRangeSet::from_ranges(busy_periods);
+
+    Ok(working_hours
+        .difference(&busy)
+        .ranges()
+        .iter()
+        .filter(|r| r.duration() >= min_duration)
+        .copied()
+        .collect())
+}
+
+/// Find slots within `search_range` where every participant is free for at
+/// least `min_duration` — the key primitive for scheduling a meeting across
+/// calendars. Takes the union of every participant's busy ranges and runs
+/// `find_free_slots` against the combined list.
+pub fn find_mutual_free_slots(
+    busy_per_participant: &[Vec<TimeRange>],
+    search_range: &TimeRange,
+    min_duration: TimeDelta,
+) -> Vec<TimeRange> {
+    let combined_busy = busy_per_participant
+        .iter()
+        .fold(TimeRangeSet::new(), |acc, busy| {
+            acc.union(&TimeRangeSet::from_ranges(busy))
+        });
+    find_free_slots(combined_busy.ranges(), search_range, min_duration)
+}
+
+/// A slot found by `find_mutual_free_slots_with_counts`, annotated with how
+/// many of the participants are actually free during it.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct MutualFreeSlot {
+    pub range: TimeRange,
+    pub available_participants: usize,
+}
+
+/// Like `find_mutual_free_slots`, but surfaces "best-effort" slots where
+/// only k of n participants are free instead of requiring unanimous
+/// availability. Sweeps every participant's interval endpoints within
+/// `search_range`, tracking a running count of how many participants are
+/// busy in each resulting window, and keeps windows at least `min_duration`
+/// long.
+pub fn find_mutual_free_slots_with_counts(
+    busy_per_participant: &[Vec<TimeRange>],
+    search_range: &TimeRange,
+    min_duration: TimeDelta,
+) -> Vec<MutualFreeSlot> {
+    let total = busy_per_participant.len();
+    let per_participant: Vec<Vec<TimeRange>> = busy_per_participant
+        .iter()
+        .map(|busy| TimeRangeSet::from_ranges(busy).ranges().to_vec())
         .collect();
 
-    busy_ranges.sort_by_key(|(r, _)| r.start);
+    let mut endpoints: Vec<DateTime<Utc>> = vec![search_range.start, search_range.end];
+    for busy in &per_participant {
+        for r in busy {
+            if r.start > search_range.start && r.start < search_range.end {
+                endpoints.push(r.start);
+            }
+            if r.end > search_range.start && r.end < search_range.end {
+                endpoints.push(r.end);
+            }
+        }
+    }
+    endpoints.sort();
+    endpoints.dedup();
+
+    let mut slots = Vec::new();
+    for window in endpoints.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        if end - start < min_duration {
+            continue;
+        }
+        let busy_count = per_participant
+            .iter()
+            .filter(|busy| busy.iter().any(|r| r.start <= start && end <= r.end))
+            .count();
+        slots.push(MutualFreeSlot {
+            range: TimeRange::new(start, end),
+            available_participants: total - busy_count,
+        });
+    }
+    slots
+}
+
+/// Registered pairwise travel durations between named locations (e.g. event
+/// `LOCATION` values), used to size location-aware buffers in
+/// `find_free_slots_with_travel` instead of a flat buffer applied everywhere.
+/// Travel time is treated as symmetric: registering `(a, b)` also answers
+/// lookups for `(b, a)`.
+#[derive(Debug, Clone, Default)]
+pub struct TravelMatrix {
+    durations: HashMap<(String, String), u32>,
+}
+
+impl TravelMatrix {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the travel time between `a` and `b`, in minutes. Overwrites
+    /// any existing entry for this pair (in either order).
+    pub fn set(&mut self, a: &str, b: &str, minutes: u32) {
+        self.durations.insert((a.to_string(), b.to_string()), minutes);
+    }
+
+    /// Look up the travel time between `a` and `b`, in minutes, trying both
+    /// orderings of the pair. `None` if neither direction was registered.
+    pub fn minutes_between(&self, a: &str, b: &str) -> Option<u32> {
+        self.durations
+            .get(&(a.to_string(), b.to_string()))
+            .or_else(|| self.durations.get(&(b.to_string(), a.to_string())))
+            .copied()
+    }
+}
+
+/// One event's busy interval plus the location it happened at (if known), for
+/// location-aware buffer calculation in `find_free_slots_with_travel`.
+#[derive(Debug, Clone)]
+pub struct LocatedBusyPeriod {
+    pub range: TimeRange,
+    pub location: Option<String>,
+}
+
+/// A merged run of overlapping `LocatedBusyPeriod`s. `leading_location` is the
+/// location of whichever period started the run (relevant to a gap ending
+/// here); `trailing_location` is the location of whichever period pushed the
+/// run's end out furthest (relevant to a gap starting here).
+struct MergedLocatedBusy {
+    range: TimeRange,
+    leading_location: Option<String>,
+    trailing_location: Option<String>,
+}
+
+fn merge_located(periods: &[LocatedBusyPeriod]) -> Vec<MergedLocatedBusy> {
+    if periods.is_empty() {
+        return vec![];
+    }
+    let mut sorted: Vec<LocatedBusyPeriod> = periods.to_vec();
+    sorted.sort_by_key(|p| p.range.start);
+
+    let mut merged = vec![MergedLocatedBusy {
+        range: sorted[0].range,
+        leading_location: sorted[0].location.clone(),
+        trailing_location: sorted[0].location.clone(),
+    }];
+    for p in &sorted[1..] {
+        let last = merged.last_mut().unwrap();
+        if p.range.start <= last.range.end {
+            if p.range.end > last.range.end {
+                last.range.end = p.range.end;
+                last.trailing_location = p.location.clone();
+            }
+        } else {
+            merged.push(MergedLocatedBusy {
+                range: p.range,
+                leading_location: p.location.clone(),
+                trailing_location: p.location.clone(),
+            });
+        }
+    }
+    merged
+}
+
+/// A free slot found by `find_free_slots_with_travel`, carrying the adjacent
+/// locations and the buffer minutes applied on each side so a caller can
+/// explain why the slot starts/ends where it does.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TravelAwareSlot {
+    pub range: TimeRange,
+    /// Location of the preceding event, if any.
+    pub preceding_location: Option<String>,
+    /// Location of the following event, if any.
+    pub following_location: Option<String>,
+    /// Minutes of leading buffer applied (travel time from `preceding_location`, or the flat default).
+    pub leading_buffer_minutes: i64,
+    /// Minutes of trailing buffer applied (travel time to `following_location`, or the flat default).
+    pub trailing_buffer_minutes: i64,
+}
+
+/// The buffer to apply between `slot_location` and `other_location`: the
+/// registered travel time if both are known and a matrix entry exists for
+/// the pair, otherwise `default_buffer`.
+fn travel_buffer(
+    travel_matrix: &TravelMatrix,
+    slot_location: Option<&str>,
+    other_location: Option<&str>,
+    default_buffer: TimeDelta,
+) -> (TimeDelta, i64) {
+    if let (Some(a), Some(b)) = (slot_location, other_location) {
+        if let Some(minutes) = travel_matrix.minutes_between(a, b) {
+            return (TimeDelta::minutes(minutes as i64), minutes as i64);
+        }
+    }
+    (default_buffer, default_buffer.num_minutes())
+}
+
+/// Find free slots of at least `min_duration` within `search_range`, sizing
+/// each gap's buffers by travel time instead of a flat amount: the leading
+/// buffer is the travel time from the preceding event's location to
+/// `slot_location`, and the trailing buffer is the travel time from
+/// `slot_location` to the following event's location. Falls back to
+/// `default_buffer` on a side when `slot_location` is `None`, the adjacent
+/// event has no location, or `travel_matrix` has no entry for the pair.
+pub fn find_free_slots_with_travel(
+    busy_periods: &[LocatedBusyPeriod],
+    search_range: &TimeRange,
+    min_duration: TimeDelta,
+    slot_location: Option<&str>,
+    default_buffer: TimeDelta,
+    travel_matrix: &TravelMatrix,
+) -> Vec<TravelAwareSlot> {
+    let merged = merge_located(busy_periods);
+
+    let mut slots = Vec::new();
+    let mut cursor = search_range.start;
+    let mut preceding_location: Option<String> = None;
+
+    for period in &merged {
+        if period.range.start > cursor {
+            let following_location = period.leading_location.clone();
+            let (leading_buffer, leading_minutes) =
+                travel_buffer(travel_matrix, slot_location, preceding_location.as_deref(), default_buffer);
+            let (trailing_buffer, trailing_minutes) =
+                travel_buffer(travel_matrix, slot_location, following_location.as_deref(), default_buffer);
+
+            let gap_start = cursor + leading_buffer;
+            let gap_end = period.range.start.min(search_range.end) - trailing_buffer;
+            if gap_end > gap_start && gap_end - gap_start >= min_duration {
+                slots.push(TravelAwareSlot {
+                    range: TimeRange::new(gap_start, gap_end),
+                    preceding_location: preceding_location.clone(),
+                    following_location,
+                    leading_buffer_minutes: leading_minutes,
+                    trailing_buffer_minutes: trailing_minutes,
+                });
+            }
+        }
+        cursor = cursor.max(period.range.end);
+        preceding_location = period.trailing_location.clone();
+        if cursor >= search_range.end {
+            return slots;
+        }
+    }
+
+    // Trailing free slot after the last busy period.
+    if cursor < search_range.end {
+        let (leading_buffer, leading_minutes) =
+            travel_buffer(travel_matrix, slot_location, preceding_location.as_deref(), default_buffer);
+        let gap_start = cursor + leading_buffer;
+        let gap_end = search_range.end;
+        if gap_end > gap_start && gap_end - gap_start >= min_duration {
+            slots.push(TravelAwareSlot {
+                range: TimeRange::new(gap_start, gap_end),
+                preceding_location,
+                following_location: None,
+                leading_buffer_minutes: leading_minutes,
+                trailing_buffer_minutes: 0,
+            });
+        }
+    }
+
+    slots
+}
+
+/// Precedence when multiple occurrences merge into one period: the
+/// "busiest" classification wins, since that's the one a caller should be
+/// warned about.
+fn dominant_transparency(a: Transparency, b: Transparency) -> Transparency {
+    fn rank(t: Transparency) -> u8 {
+        match t {
+            Transparency::Busy => 3,
+            Transparency::OutOfOffice => 2,
+            Transparency::Tentative => 1,
+            Transparency::Free => 0,
+        }
+    }
+    if rank(b) > rank(a) { b } else { a }
+}
+
+/// Merge a set of (possibly overlapping) busy ranges, each tagged with the
+/// title of the event that produced it, into sorted non-overlapping
+/// `BusyPeriod`s. Shared by `compute_free_busy` and `BusyIndex::free_busy`.
+fn merge_busy_with_titles(mut ranges: Vec<(TimeRange, String, Transparency)>) -> Vec<BusyPeriod> {
+    ranges.sort_by_key(|(r, _, _)| r.start);
 
-    // Build busy periods with merged ranges and associated titles
     let mut busy_periods: Vec<BusyPeriod> = Vec::new();
-    for (r, title) in &busy_ranges {
+    for (r, title, transparency) in &ranges {
         if let Some(last) = busy_periods.last_mut() {
             if r.start <= last.range.end {
                 last.range.end = last.range.end.max(r.end);
                 if !last.event_titles.contains(title) {
                     last.event_titles.push(title.clone());
                 }
+                last.transparency = dominant_transparency(last.transparency, *transparency);
                 continue;
             }
         }
         busy_periods.push(BusyPeriod {
             range: *r,
             event_titles: vec![title.clone()],
+            transparency: *transparency,
         });
     }
+    busy_periods
+}
+
+/// Clip each occurrence to `range`, split by transparency: `Free` occurrences
+/// are dropped entirely, `Tentative` ones go in the second list, everything
+/// else (including `OutOfOffice`) goes in the first.
+fn clipped_ranges_by_transparency(
+    occurrences: &[EventOccurrence],
+    range: &TimeRange,
+) -> (
+    Vec<(TimeRange, String, Transparency)>,
+    Vec<(TimeRange, String, Transparency)>,
+) {
+    let mut busy = Vec::new();
+    let mut tentative = Vec::new();
+    for occ in occurrences {
+        if occ.transparency == Transparency::Free {
+            continue;
+        }
+        let occ_range = TimeRange::new(occ.start, occ.end);
+        if !occ_range.overlaps(range) {
+            continue;
+        }
+        let clipped = TimeRange::new(occ.start.max(range.start), occ.end.min(range.end));
+        let entry = (clipped, occ.title.clone(), occ.transparency);
+        if occ.transparency == Transparency::Tentative {
+            tentative.push(entry);
+        } else {
+            busy.push(entry);
+        }
+    }
+    (busy, tentative)
+}
+
+/// Compute free/busy breakdown for a time range given event occurrences.
+/// `Free`/transparent occurrences never block time; `Tentative` ones are
+/// reported in `tentative_periods` rather than `busy_periods`, and only
+/// count against `free_periods` when `count_tentative_as_busy` is set.
+pub fn compute_free_busy(
+    occurrences: &[EventOccurrence],
+    range: &TimeRange,
+    count_tentative_as_busy: bool,
+) -> FreeBusyResult {
+    if occurrences.is_empty() {
+        return FreeBusyResult {
+            busy_periods: vec![],
+            tentative_periods: vec![],
+            free_periods: vec![*range],
+            total_busy_minutes: 0,
+            total_free_minutes: range.duration().num_minutes(),
+        };
+    }
+
+    let (busy_ranges, tentative_ranges) = clipped_ranges_by_transparency(occurrences, range);
 
-    let busy_only: Vec<TimeRange> = busy_periods.iter().map(|bp| bp.range).collect();
-    let free_periods = find_free_slots(&busy_only, range, TimeDelta::zero());
+    let busy_periods = merge_busy_with_titles(busy_ranges);
+    let tentative_periods = merge_busy_with_titles(tentative_ranges);
+
+    let mut occupied: Vec<TimeRange> = busy_periods.iter().map(|bp| bp.range).collect();
+    if count_tentative_as_busy {
+        occupied.extend(tentative_periods.iter().map(|bp| bp.range));
+    }
+    let free_periods = find_free_slots(&occupied, range, TimeDelta::zero());
 
     let total_busy_minutes: i64 = busy_periods
         .iter()
@@ -167,16 +684,204 @@ pub fn compute_free_busy(
 
     FreeBusyResult {
         busy_periods,
+        tentative_periods,
         free_periods,
         total_busy_minutes,
         total_free_minutes,
     }
 }
 
+/// A reusable index over a fixed set of event occurrences, for callers that
+/// query free/busy across many different windows (e.g. a per-day agenda
+/// sweep, or free/busy for several attendees) without re-sorting and
+/// re-scanning the full occurrence set on every call. Built once via `new`;
+/// `find_overlapping` then answers "which busy ranges intersect this window"
+/// in roughly O(log n + k) rather than O(n), in the style of a Lapper
+/// interval store: intervals are sorted by `start`, and `max_len` (the
+/// widest interval in the set) bounds how far before a query's start we ever
+/// need to look.
+pub struct BusyIndex {
+    /// (range, event title, transparency), sorted ascending by `range.start`.
+    intervals: Vec<(TimeRange, String, Transparency)>,
+    /// The longest interval's duration; no interval overlapping a query
+    /// starting at `qs` can start before `qs - max_len`.
+    max_len: TimeDelta,
+}
+
+impl BusyIndex {
+    pub fn new(occurrences: &[EventOccurrence]) -> Self {
+        let mut intervals: Vec<(TimeRange, String, Transparency)> = occurrences
+            .iter()
+            .map(|occ| (TimeRange::new(occ.start, occ.end), occ.title.clone(), occ.transparency))
+            .collect();
+        intervals.sort_by_key(|(r, _, _)| r.start);
+        let max_len = intervals
+            .iter()
+            .map(|(r, _, _)| r.duration())
+            .max()
+            .unwrap_or(TimeDelta::zero());
+        Self { intervals, max_len }
+    }
+
+    /// All indexed intervals overlapping the half-open `query` range, found
+    /// by binary-searching for the first interval that could possibly
+    /// overlap and scanning forward until intervals start at or past
+    /// `query.end`.
+    pub fn find_overlapping(&self, query: &TimeRange) -> Vec<(TimeRange, String, Transparency)> {
+        let lower_bound = query.start - self.max_len;
+        let start_idx = self.intervals.partition_point(|(r, _, _)| r.start < lower_bound);
+        self.intervals[start_idx..]
+            .iter()
+            .take_while(|(r, _, _)| r.start < query.end)
+            .filter(|(r, _, _)| r.end > query.start)
+            .cloned()
+            .collect()
+    }
+
+    /// As `compute_free_busy`, but answered from the index instead of
+    /// re-scanning every occurrence.
+    pub fn free_busy(&self, range: &TimeRange, count_tentative_as_busy: bool) -> FreeBusyResult {
+        let mut busy_ranges = Vec::new();
+        let mut tentative_ranges = Vec::new();
+        for (r, title, transparency) in self.find_overlapping(range) {
+            if transparency == Transparency::Free {
+                continue;
+            }
+            let clipped = TimeRange::new(r.start.max(range.start), r.end.min(range.end));
+            let entry = (clipped, title, transparency);
+            if transparency == Transparency::Tentative {
+                tentative_ranges.push(entry);
+            } else {
+                busy_ranges.push(entry);
+            }
+        }
+
+        if busy_ranges.is_empty() && tentative_ranges.is_empty() {
+            return FreeBusyResult {
+                busy_periods: vec![],
+                tentative_periods: vec![],
+                free_periods: vec![*range],
+                total_busy_minutes: 0,
+                total_free_minutes: range.duration().num_minutes(),
+            };
+        }
+
+        let busy_periods = merge_busy_with_titles(busy_ranges);
+        let tentative_periods = merge_busy_with_titles(tentative_ranges);
+
+        let mut occupied: Vec<TimeRange> = busy_periods.iter().map(|bp| bp.range).collect();
+        if count_tentative_as_busy {
+            occupied.extend(tentative_periods.iter().map(|bp| bp.range));
+        }
+        let free_periods = find_free_slots(&occupied, range, TimeDelta::zero());
+
+        let total_busy_minutes: i64 = busy_periods
+            .iter()
+            .map(|bp| bp.range.duration().num_minutes())
+            .sum();
+        let total_free_minutes = range.duration().num_minutes() - total_busy_minutes;
+
+        FreeBusyResult {
+            busy_periods,
+            tentative_periods,
+            free_periods,
+            total_busy_minutes,
+            total_free_minutes,
+        }
+    }
+
+    /// As `find_free_slots`, but answered from the index instead of
+    /// re-scanning every occurrence.
+    pub fn find_free_slots(&self, range: &TimeRange, min_duration: TimeDelta) -> Vec<TimeRange> {
+        let busy: Vec<TimeRange> = self
+            .find_overlapping(range)
+            .into_iter()
+            .map(|(r, _, _)| TimeRange::new(r.start.max(range.start), r.end.min(range.end)))
+            .collect();
+        find_free_slots(&busy, range, min_duration)
+    }
+}
+
+/// A single calendar day's worth of occurrences, in a requested local timezone.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AgendaDay {
+    /// Calendar date in the requested timezone, as `YYYY-MM-DD`.
+    pub date: String,
+    /// Occurrences overlapping this day, sorted by start time. A multi-day
+    /// occurrence appears under every day it overlaps.
+    pub events: Vec<EventOccurrence>,
+}
+
+/// Resolve local midnight on `date` in `tz` to the UTC instant it
+/// represents. Errors if midnight falls in a DST spring-forward gap on
+/// `date` (rare, but not impossible for some zones' transition rules).
+pub(crate) fn local_midnight_utc(date: NaiveDate, tz: Tz) -> Result<DateTime<Utc>, TempoError> {
+    match tz.from_local_datetime(&date.and_time(NaiveTime::MIN)) {
+        chrono::LocalResult::Single(dt) => Ok(dt.with_timezone(&Utc)),
+        chrono::LocalResult::Ambiguous(earlier, _later) => Ok(earlier.with_timezone(&Utc)),
+        chrono::LocalResult::None => Err(TempoError::InvalidTimeRange(format!(
+            "midnight on {} does not exist in {} (falls in a DST gap)",
+            date, tz
+        ))),
+    }
+}
+
+/// Bucket occurrences into a day-by-day agenda covering every local calendar
+/// day in `range`, in `tz`. A multi-day occurrence (start and end on
+/// different local days) is carried forward so it appears under every day it
+/// overlaps, not just the day it starts.
+pub fn build_agenda(occurrences: &[EventOccurrence], range: &TimeRange, tz: Tz) -> Result<Vec<AgendaDay>, TempoError> {
+    let first_day = range.start.with_timezone(&tz).date_naive();
+    let last_day = (range.end - TimeDelta::nanoseconds(1))
+        .with_timezone(&tz)
+        .date_naive();
+    if first_day > last_day {
+        return Ok(vec![]);
+    }
+
+    let mut sorted: Vec<EventOccurrence> = occurrences.to_vec();
+    sorted.sort_by_key(|o| o.start);
+
+    let mut days = Vec::new();
+    let mut not_over_yet: Vec<EventOccurrence> = Vec::new();
+    let mut idx = 0;
+    let mut day = first_day;
+    loop {
+        let day_start = local_midnight_utc(day, tz)?;
+        let day_end = local_midnight_utc(day.succ_opt().unwrap(), tz)?;
+        let day_range = TimeRange::new(day_start, day_end);
+
+        // Carry forward occurrences that started on or before today...
+        while idx < sorted.len() && sorted[idx].start < day_end {
+            not_over_yet.push(sorted[idx].clone());
+            idx += 1;
+        }
+        // ...and drop ones that are fully over by today.
+        not_over_yet.retain(|o| o.end > day_start);
+
+        let mut todays: Vec<EventOccurrence> = not_over_yet
+            .iter()
+            .filter(|o| TimeRange::new(o.start, o.end).overlaps(&day_range))
+            .cloned()
+            .collect();
+        todays.sort_by_key(|o| o.start);
+
+        days.push(AgendaDay {
+            date: day.format("%Y-%m-%d").to_string(),
+            events: todays,
+        });
+
+        if day == last_day {
+            break;
+        }
+        day = day.succ_opt().unwrap();
+    }
+    Ok(days)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::TimeZone;
 
     fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
         Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
@@ -295,13 +1000,257 @@ mod tests {
         assert!(slots.is_empty());
     }
 
+    // -- find_free_slots_within_hours --
+
+    #[test]
+    fn free_slots_within_hours_restricts_to_working_windows() {
+        // Mon Jan 6 2025 - Fri Jan 10 2025, 9-5 Mon-Fri, UTC.
+        let mut availability = WeeklyAvailability::new(chrono_tz::UTC);
+        for weekday in [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ] {
+            availability.add_window(
+                weekday,
+                NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            );
+        }
+
+        // Search a single Monday, with a meeting 10-11.
+        let range = TimeRange::new(utc(2025, 1, 6, 0), utc(2025, 1, 7, 0));
+        let busy = vec![TimeRange::new(utc(2025, 1, 6, 10), utc(2025, 1, 6, 11))];
+
+        let slots = find_free_slots_within_hours(&busy, &range, mins(30), &availability).unwrap();
+        assert_eq!(
+            slots,
+            vec![
+                TimeRange::new(utc(2025, 1, 6, 9), utc(2025, 1, 6, 10)),
+                TimeRange::new(utc(2025, 1, 6, 11), utc(2025, 1, 6, 17)),
+            ]
+        );
+    }
+
+    #[test]
+    fn free_slots_within_hours_weekend_has_no_availability() {
+        let mut availability = WeeklyAvailability::new(chrono_tz::UTC);
+        availability.add_window(
+            Weekday::Mon,
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        );
+
+        // Saturday Jan 11 2025 has no registered window.
+        let range = TimeRange::new(utc(2025, 1, 11, 0), utc(2025, 1, 12, 0));
+        let slots = find_free_slots_within_hours(&[], &range, mins(30), &availability).unwrap();
+        assert!(slots.is_empty());
+    }
+
+    #[test]
+    fn free_slots_within_hours_spans_multiple_days() {
+        let mut availability = WeeklyAvailability::new(chrono_tz::UTC);
+        availability.add_window(
+            Weekday::Mon,
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+        );
+        availability.add_window(
+            Weekday::Tue,
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+        );
+
+        let range = TimeRange::new(utc(2025, 1, 6, 0), utc(2025, 1, 8, 0));
+        let slots = find_free_slots_within_hours(&[], &range, mins(30), &availability).unwrap();
+        assert_eq!(
+            slots,
+            vec![
+                TimeRange::new(utc(2025, 1, 6, 9), utc(2025, 1, 6, 12)),
+                TimeRange::new(utc(2025, 1, 7, 9), utc(2025, 1, 7, 12)),
+            ]
+        );
+    }
+
+    #[test]
+    fn free_slots_within_hours_handles_dst_spring_forward() {
+        // America/New_York springs forward on 2025-03-09: 2am -> 3am local.
+        // A 9-17 local window should still resolve to a valid (if shorter) UTC range.
+        let tz: chrono_tz::Tz = "America/New_York".parse().unwrap();
+        let mut availability = WeeklyAvailability::new(tz);
+        availability.add_window(
+            Weekday::Sun,
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        );
+
+        let range = TimeRange::new(utc(2025, 3, 9, 0), utc(2025, 3, 10, 0));
+        let slots = find_free_slots_within_hours(&[], &range, mins(30), &availability).unwrap();
+        assert_eq!(slots.len(), 1);
+        // Clocks spring forward at 2am local, so 9am-5pm that day is already in
+        // EDT (UTC-4): 9am -> 13:00 UTC, 5pm -> 21:00 UTC.
+        assert_eq!(slots[0].start, utc(2025, 3, 9, 13));
+        assert_eq!(slots[0].end, utc(2025, 3, 9, 21));
+    }
+
+    #[test]
+    fn free_slots_within_hours_errors_on_a_window_straddling_the_dst_gap() {
+        // America/New_York springs forward on 2025-03-09: 2am -> 3am local,
+        // so 02:00-04:00 has no valid UTC instant for its start.
+        let tz: chrono_tz::Tz = "America/New_York".parse().unwrap();
+        let mut availability = WeeklyAvailability::new(tz);
+        availability.add_window(
+            Weekday::Sun,
+            NaiveTime::from_hms_opt(2, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(4, 0, 0).unwrap(),
+        );
+
+        let range = TimeRange::new(utc(2025, 3, 9, 0), utc(2025, 3, 10, 0));
+        let result = find_free_slots_within_hours(&[], &range, mins(30), &availability);
+        assert!(matches!(result, Err(TempoError::InvalidTimeRange(_))));
+    }
+
+    // -- find_mutual_free_slots --
+
+    #[test]
+    fn mutual_free_slots_require_all_participants_free() {
+        let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let alice = vec![TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10))];
+        let bob = vec![TimeRange::new(utc(2025, 1, 1, 14), utc(2025, 1, 1, 15))];
+        let slots = find_mutual_free_slots(&[alice, bob], &range, mins(30));
+        assert_eq!(slots.len(), 3);
+        assert_eq!(slots[0], TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 9)));
+        assert_eq!(slots[1], TimeRange::new(utc(2025, 1, 1, 10), utc(2025, 1, 1, 14)));
+        assert_eq!(slots[2], TimeRange::new(utc(2025, 1, 1, 15), utc(2025, 1, 1, 17)));
+    }
+
+    #[test]
+    fn mutual_free_slots_with_no_participants_is_fully_free() {
+        let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let slots = find_mutual_free_slots(&[], &range, mins(30));
+        assert_eq!(slots, vec![range]);
+    }
+
+    #[test]
+    fn mutual_free_slots_with_counts_reports_best_effort_availability() {
+        let range = TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 12));
+        let alice = vec![TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10))];
+        let bob = vec![TimeRange::new(utc(2025, 1, 1, 10), utc(2025, 1, 1, 11))];
+        let slots = find_mutual_free_slots_with_counts(&[alice, bob], &range, mins(30));
+        assert_eq!(
+            slots
+                .iter()
+                .map(|s| (s.range, s.available_participants))
+                .collect::<Vec<_>>(),
+            vec![
+                (TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)), 1),
+                (TimeRange::new(utc(2025, 1, 1, 10), utc(2025, 1, 1, 11)), 1),
+                (TimeRange::new(utc(2025, 1, 1, 11), utc(2025, 1, 1, 12)), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn mutual_free_slots_with_counts_filters_by_min_duration() {
+        let range = TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 12));
+        let alice = vec![TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 11))];
+        // Remaining 11-12 gap is only 60 minutes, shorter than the 90 min minimum.
+        let slots = find_mutual_free_slots_with_counts(&[alice], &range, mins(90));
+        assert!(slots.is_empty());
+    }
+
+    // -- TravelMatrix --
+
+    #[test]
+    fn travel_matrix_lookup_is_symmetric() {
+        let mut matrix = TravelMatrix::new();
+        matrix.set("Office", "Airport", 45);
+        assert_eq!(matrix.minutes_between("Office", "Airport"), Some(45));
+        assert_eq!(matrix.minutes_between("Airport", "Office"), Some(45));
+    }
+
+    #[test]
+    fn travel_matrix_unknown_pair_returns_none() {
+        let matrix = TravelMatrix::new();
+        assert_eq!(matrix.minutes_between("Office", "Airport"), None);
+    }
+
+    // -- find_free_slots_with_travel --
+
+    fn located(start: DateTime<Utc>, end: DateTime<Utc>, location: Option<&str>) -> LocatedBusyPeriod {
+        LocatedBusyPeriod {
+            range: TimeRange::new(start, end),
+            location: location.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn travel_aware_slots_use_flat_buffer_without_location() {
+        let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let busy = vec![
+            located(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10), Some("Office")),
+            located(utc(2025, 1, 1, 14), utc(2025, 1, 1, 15), Some("Airport")),
+        ];
+        let slots = find_free_slots_with_travel(
+            &busy,
+            &range,
+            mins(30),
+            None,
+            mins(15),
+            &TravelMatrix::new(),
+        );
+        assert_eq!(slots.len(), 3);
+        // No slot_location, so every buffer falls back to the flat default.
+        for slot in &slots {
+            assert!(slot.leading_buffer_minutes == 15 || slot.leading_buffer_minutes == 0);
+        }
+        assert_eq!(slots[1].range, TimeRange::new(utc(2025, 1, 1, 10) + mins(15), utc(2025, 1, 1, 14) - mins(15)));
+    }
+
+    #[test]
+    fn travel_aware_slots_use_registered_travel_time() {
+        let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let busy = vec![
+            located(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10), Some("Office")),
+            located(utc(2025, 1, 1, 14), utc(2025, 1, 1, 15), Some("Airport")),
+        ];
+        let mut matrix = TravelMatrix::new();
+        matrix.set("Office", "Gym", 20);
+        matrix.set("Gym", "Airport", 40);
+
+        let slots = find_free_slots_with_travel(&busy, &range, mins(30), Some("Gym"), mins(15), &matrix);
+
+        // The middle gap (10:00-14:00) should be shrunk by the registered
+        // travel times (20min from Office, 40min to Airport), not the flat 15min default.
+        let middle = slots.iter().find(|s| s.preceding_location.as_deref() == Some("Office")).unwrap();
+        assert_eq!(middle.leading_buffer_minutes, 20);
+        assert_eq!(middle.trailing_buffer_minutes, 40);
+        assert_eq!(middle.range.start, utc(2025, 1, 1, 10) + mins(20));
+        assert_eq!(middle.range.end, utc(2025, 1, 1, 14) - mins(40));
+    }
+
+    #[test]
+    fn travel_aware_slots_fall_back_when_pair_unknown() {
+        let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let busy = vec![located(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10), Some("Office"))];
+        let matrix = TravelMatrix::new(); // no entries registered
+
+        let slots = find_free_slots_with_travel(&busy, &range, mins(30), Some("Gym"), mins(15), &matrix);
+
+        let after = slots.iter().find(|s| s.preceding_location.as_deref() == Some("Office")).unwrap();
+        assert_eq!(after.leading_buffer_minutes, 15);
+    }
+
     // -- compute_free_busy --
 
     #[test]
     fn free_busy_with_no_events() {
         let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
-        let result = compute_free_busy(&[], &range);
+        let result = compute_free_busy(&[], &range, false);
         assert!(result.busy_periods.is_empty());
+        assert!(result.tentative_periods.is_empty());
         assert_eq!(result.free_periods.len(), 1);
         assert_eq!(result.total_busy_minutes, 0);
         assert_eq!(result.total_free_minutes, 540); // 9 hours
@@ -317,13 +1266,345 @@ mod tests {
                 start: utc(2025, 1, 1, 9),
                 end: utc(2025, 1, 1, 10),
                 is_recurring: false,
+            is_all_day: false,
+                transparency: Transparency::Busy,
                 metadata: Default::default(),
             },
         ];
-        let result = compute_free_busy(&occurrences, &range);
+        let result = compute_free_busy(&occurrences, &range, false);
         assert_eq!(result.busy_periods.len(), 1);
         assert_eq!(result.busy_periods[0].event_titles, vec!["Meeting"]);
         assert_eq!(result.total_busy_minutes, 60);
         assert_eq!(result.total_free_minutes, 180);
     }
+
+    #[test]
+    fn free_busy_excludes_transparent_events() {
+        let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 12));
+        let mut metadata = HashMap::new();
+        metadata.insert("transp".to_string(), "TRANSPARENT".to_string());
+        let occurrences = vec![EventOccurrence {
+            event_id: super::super::event::EventId::new(),
+            title: "Focus time".to_string(),
+            start: utc(2025, 1, 1, 9),
+            end: utc(2025, 1, 1, 10),
+            is_recurring: false,
+            is_all_day: false,
+            transparency: Transparency::from_metadata(&metadata),
+            metadata,
+        }];
+        let result = compute_free_busy(&occurrences, &range, false);
+        assert!(result.busy_periods.is_empty());
+        assert!(result.tentative_periods.is_empty());
+        assert_eq!(result.total_free_minutes, 240);
+    }
+
+    #[test]
+    fn free_busy_reports_tentative_separately_from_busy() {
+        let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 12));
+        let mut metadata = HashMap::new();
+        metadata.insert("status".to_string(), "TENTATIVE".to_string());
+        let occurrences = vec![
+            EventOccurrence {
+                event_id: super::super::event::EventId::new(),
+                title: "Maybe".to_string(),
+                start: utc(2025, 1, 1, 9),
+                end: utc(2025, 1, 1, 10),
+                is_recurring: false,
+                is_all_day: false,
+                transparency: Transparency::from_metadata(&metadata),
+                metadata,
+            },
+            occ("Confirmed", utc(2025, 1, 1, 10), utc(2025, 1, 1, 11)),
+        ];
+
+        let without_tentative = compute_free_busy(&occurrences, &range, false);
+        assert_eq!(without_tentative.busy_periods.len(), 1);
+        assert_eq!(without_tentative.busy_periods[0].event_titles, vec!["Confirmed"]);
+        assert_eq!(without_tentative.tentative_periods.len(), 1);
+        assert_eq!(without_tentative.tentative_periods[0].event_titles, vec!["Maybe"]);
+        assert_eq!(without_tentative.total_free_minutes, 180); // 8-9 and 11-12 free
+
+        let with_tentative = compute_free_busy(&occurrences, &range, true);
+        assert_eq!(with_tentative.total_free_minutes, 120); // only 11-12 free
+    }
+
+    // -- BusyIndex --
+
+    fn occ(title: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> EventOccurrence {
+        EventOccurrence {
+            event_id: super::super::event::EventId::new(),
+            title: title.to_string(),
+            start,
+            end,
+            is_recurring: false,
+            is_all_day: false,
+            transparency: Transparency::Busy,
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn busy_index_find_overlapping_matches_linear_scan() {
+        let occs = vec![
+            occ("A", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)),
+            occ("B", utc(2025, 1, 1, 13), utc(2025, 1, 1, 15)),
+            occ("C", utc(2025, 1, 1, 20), utc(2025, 1, 1, 21)),
+        ];
+        let index = BusyIndex::new(&occs);
+
+        let query = TimeRange::new(utc(2025, 1, 1, 14), utc(2025, 1, 1, 20));
+        let found = index.find_overlapping(&query);
+        let titles: Vec<&str> = found.iter().map(|(_, t, _)| t.as_str()).collect();
+        assert_eq!(titles, vec!["B"]);
+    }
+
+    #[test]
+    fn busy_index_finds_long_interval_starting_well_before_query() {
+        // A's interval starts long before the query window but still overlaps it;
+        // the max_len back-off must not miss it.
+        let occs = vec![occ("A", utc(2025, 1, 1, 0), utc(2025, 1, 2, 12))];
+        let index = BusyIndex::new(&occs);
+
+        let query = TimeRange::new(utc(2025, 1, 2, 10), utc(2025, 1, 2, 11));
+        let found = index.find_overlapping(&query);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1, "A");
+    }
+
+    #[test]
+    fn busy_index_free_busy_matches_compute_free_busy() {
+        let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let occs = vec![
+            occ("Meeting", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)),
+            occ("Lunch", utc(2025, 1, 1, 12), utc(2025, 1, 1, 13)),
+        ];
+
+        let direct = compute_free_busy(&occs, &range, false);
+        let indexed = BusyIndex::new(&occs).free_busy(&range, false);
+
+        assert_eq!(direct.total_busy_minutes, indexed.total_busy_minutes);
+        assert_eq!(direct.free_periods, indexed.free_periods);
+        assert_eq!(direct.busy_periods.len(), indexed.busy_periods.len());
+    }
+
+    #[test]
+    fn busy_index_find_free_slots_matches_find_free_slots() {
+        let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let occs = vec![
+            occ("Meeting", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)),
+            occ("Lunch", utc(2025, 1, 1, 14), utc(2025, 1, 1, 15)),
+        ];
+        let busy: Vec<TimeRange> = occs.iter().map(|o| TimeRange::new(o.start, o.end)).collect();
+
+        let direct = find_free_slots(&busy, &range, mins(30));
+        let indexed = BusyIndex::new(&occs).find_free_slots(&range, mins(30));
+
+        assert_eq!(direct, indexed);
+    }
+
+    #[test]
+    fn busy_index_empty_returns_whole_range_free() {
+        let range = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let index = BusyIndex::new(&[]);
+        let result = index.free_busy(&range, false);
+        assert!(result.busy_periods.is_empty());
+        assert_eq!(result.free_periods, vec![range]);
+    }
+
+    // -- TimeRangeSet --
+
+    #[test]
+    fn from_ranges_normalizes_like_merge_ranges() {
+        let set = TimeRangeSet::from_ranges(&[
+            TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 11)),
+            TimeRange::new(utc(2025, 1, 1, 10), utc(2025, 1, 1, 12)),
+            TimeRange::new(utc(2025, 1, 1, 14), utc(2025, 1, 1, 15)),
+        ]);
+        assert_eq!(
+            set.ranges(),
+            &[
+                TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 12)),
+                TimeRange::new(utc(2025, 1, 1, 14), utc(2025, 1, 1, 15)),
+            ]
+        );
+    }
+
+    #[test]
+    fn union_combines_and_merges_both_sets() {
+        let a = TimeRangeSet::from_ranges(&[TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10))]);
+        let b = TimeRangeSet::from_ranges(&[
+            TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 11)),
+            TimeRange::new(utc(2025, 1, 1, 13), utc(2025, 1, 1, 14)),
+        ]);
+        let union = a.union(&b);
+        assert_eq!(
+            union.ranges(),
+            &[
+                TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 11)),
+                TimeRange::new(utc(2025, 1, 1, 13), utc(2025, 1, 1, 14)),
+            ]
+        );
+    }
+
+    #[test]
+    fn intersect_walks_interleaved_ranges_with_two_cursors() {
+        let a = TimeRangeSet::from_ranges(&[
+            TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 12)),
+            TimeRange::new(utc(2025, 1, 1, 14), utc(2025, 1, 1, 16)),
+        ]);
+        let b = TimeRangeSet::from_ranges(&[
+            TimeRange::new(utc(2025, 1, 1, 10), utc(2025, 1, 1, 11)),
+            TimeRange::new(utc(2025, 1, 1, 11), utc(2025, 1, 1, 15)),
+        ]);
+        let intersection = a.intersect(&b);
+        assert_eq!(
+            intersection.ranges(),
+            &[
+                TimeRange::new(utc(2025, 1, 1, 10), utc(2025, 1, 1, 11)),
+                TimeRange::new(utc(2025, 1, 1, 11), utc(2025, 1, 1, 12)),
+                TimeRange::new(utc(2025, 1, 1, 14), utc(2025, 1, 1, 15)),
+            ]
+        );
+    }
+
+    #[test]
+    fn intersect_with_no_overlap_is_empty() {
+        let a = TimeRangeSet::from_ranges(&[TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10))]);
+        let b = TimeRangeSet::from_ranges(&[TimeRange::new(utc(2025, 1, 1, 11), utc(2025, 1, 1, 12))]);
+        assert!(a.intersect(&b).is_empty());
+    }
+
+    #[test]
+    fn difference_removes_fully_covered_range() {
+        let a = TimeRangeSet::from_ranges(&[TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10))]);
+        let b = TimeRangeSet::from_ranges(&[TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 11))]);
+        assert!(a.difference(&b).is_empty());
+    }
+
+    #[test]
+    fn difference_splits_range_around_a_hole() {
+        let a = TimeRangeSet::from_ranges(&[TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 12))]);
+        let b = TimeRangeSet::from_ranges(&[TimeRange::new(utc(2025, 1, 1, 10), utc(2025, 1, 1, 11))]);
+        let diff = a.difference(&b);
+        assert_eq!(
+            diff.ranges(),
+            &[
+                TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)),
+                TimeRange::new(utc(2025, 1, 1, 11), utc(2025, 1, 1, 12)),
+            ]
+        );
+    }
+
+    #[test]
+    fn difference_with_no_overlap_is_unchanged() {
+        let a = TimeRangeSet::from_ranges(&[TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10))]);
+        let b = TimeRangeSet::from_ranges(&[TimeRange::new(utc(2025, 1, 1, 14), utc(2025, 1, 1, 15))]);
+        assert_eq!(a.difference(&b).ranges(), a.ranges());
+    }
+
+    #[test]
+    fn complement_of_empty_set_is_the_whole_range() {
+        let within = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let empty = TimeRangeSet::new();
+        assert_eq!(empty.complement(within).ranges(), &[within]);
+    }
+
+    #[test]
+    fn complement_of_a_set_covering_the_whole_range_is_empty() {
+        let within = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let set = TimeRangeSet::from_ranges(&[within]);
+        assert!(set.complement(within).is_empty());
+    }
+
+    #[test]
+    fn complement_leaves_gaps_between_covered_ranges() {
+        let within = TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 17));
+        let set = TimeRangeSet::from_ranges(&[
+            TimeRange::new(utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)),
+            TimeRange::new(utc(2025, 1, 1, 14), utc(2025, 1, 1, 15)),
+        ]);
+        let complement = set.complement(within);
+        assert_eq!(
+            complement.ranges(),
+            &[
+                TimeRange::new(utc(2025, 1, 1, 8), utc(2025, 1, 1, 9)),
+                TimeRange::new(utc(2025, 1, 1, 10), utc(2025, 1, 1, 14)),
+                TimeRange::new(utc(2025, 1, 1, 15), utc(2025, 1, 1, 17)),
+            ]
+        );
+    }
+
+    // -- build_agenda --
+
+    fn make_occ(title: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> EventOccurrence {
+        EventOccurrence {
+            event_id: super::super::event::EventId::new(),
+            title: title.to_string(),
+            start,
+            end,
+            is_recurring: false,
+            is_all_day: false,
+            transparency: Transparency::Busy,
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn agenda_buckets_events_by_day() {
+        let range = TimeRange::new(utc(2025, 1, 1, 0), utc(2025, 1, 3, 0));
+        let occurrences = vec![
+            make_occ("Day 1 meeting", utc(2025, 1, 1, 9), utc(2025, 1, 1, 10)),
+            make_occ("Day 2 meeting", utc(2025, 1, 2, 14), utc(2025, 1, 2, 15)),
+        ];
+
+        let agenda = build_agenda(&occurrences, &range, chrono_tz::UTC).unwrap();
+        assert_eq!(agenda.len(), 2);
+        assert_eq!(agenda[0].date, "2025-01-01");
+        assert_eq!(agenda[0].events.len(), 1);
+        assert_eq!(agenda[0].events[0].title, "Day 1 meeting");
+        assert_eq!(agenda[1].date, "2025-01-02");
+        assert_eq!(agenda[1].events[0].title, "Day 2 meeting");
+    }
+
+    #[test]
+    fn agenda_carries_multi_day_event_across_days() {
+        let range = TimeRange::new(utc(2025, 1, 1, 0), utc(2025, 1, 4, 0));
+        let occurrences = vec![make_occ(
+            "Conference",
+            utc(2025, 1, 1, 9),
+            utc(2025, 1, 3, 17),
+        )];
+
+        let agenda = build_agenda(&occurrences, &range, chrono_tz::UTC).unwrap();
+        assert_eq!(agenda.len(), 3);
+        for day in &agenda {
+            assert_eq!(day.events.len(), 1);
+            assert_eq!(day.events[0].title, "Conference");
+        }
+    }
+
+    #[test]
+    fn agenda_respects_requested_timezone() {
+        // 11pm US/Eastern on Jan 1 is 4am UTC on Jan 2 - the agenda should
+        // bucket it under the *local* day, not the UTC day.
+        let range = TimeRange::new(utc(2025, 1, 1, 0), utc(2025, 1, 3, 0));
+        let occurrences = vec![make_occ(
+            "Late night call",
+            utc(2025, 1, 2, 4),
+            utc(2025, 1, 2, 5),
+        )];
+
+        let agenda = build_agenda(&occurrences, &range, chrono_tz::US::Eastern).unwrap();
+        let day_with_event = agenda.iter().find(|d| !d.events.is_empty()).unwrap();
+        assert_eq!(day_with_event.date, "2025-01-01");
+    }
+
+    #[test]
+    fn agenda_empty_when_no_events() {
+        let range = TimeRange::new(utc(2025, 1, 1, 0), utc(2025, 1, 2, 0));
+        let agenda = build_agenda(&[], &range, chrono_tz::UTC).unwrap();
+        assert_eq!(agenda.len(), 1);
+        assert!(agenda[0].events.is_empty());
+    }
 }
@@ -22,4 +22,7 @@ pub enum TempoError {
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    #[error("Calendar subscription error: {0}")]
+    SubscriptionFailed(String),
 }
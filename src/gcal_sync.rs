@@ -0,0 +1,125 @@
+//! Fetches events directly from the Google Calendar API (`events.list`)
+//! instead of requiring the caller to hand-paste the response JSON. Handles
+//! `pageToken` pagination for a full fetch and `syncToken` for an
+//! incremental delta that only returns changed/deleted events, so
+//! `sync_google_calendar` can be a first-class client rather than a paste
+//! target.
+
+use serde::Deserialize;
+
+use crate::calendar::event::Event;
+use crate::error::TempoError;
+use crate::server::{GCalEvent, gcal_event_to_event};
+
+/// One page of `events.list`, deserialized from Google's camelCase JSON.
+#[derive(Debug, Deserialize)]
+struct EventsListResponse {
+    #[serde(default)]
+    items: Vec<GCalEvent>,
+    #[serde(rename = "nextPageToken")]
+    next_page_token: Option<String>,
+    #[serde(rename = "nextSyncToken")]
+    next_sync_token: Option<String>,
+}
+
+/// The outcome of a full or incremental `events.list` fetch.
+pub struct SyncResult {
+    pub added_or_updated: Vec<Event>,
+    /// `google_calendar_id` values of events Google reported as cancelled.
+    pub removed_google_ids: Vec<String>,
+    /// The cursor to pass as `syncToken` on the next incremental fetch, if
+    /// Google returned one (it does once the final page of a sync is read).
+    pub next_sync_token: Option<String>,
+}
+
+/// Fetch `calendar_id`'s events, paginating through every `nextPageToken`.
+///
+/// If `sync_token` is `Some`, this is an incremental delta fetch: Google
+/// returns only events that changed (or were deleted, as `status:
+/// "cancelled"`) since that token was issued, and `time_min`/`time_max` are
+/// ignored. Otherwise it's a full fetch bounded by `time_min`/`time_max`.
+pub async fn fetch_events(
+    access_token: &str,
+    calendar_id: &str,
+    time_min: Option<&str>,
+    time_max: Option<&str>,
+    sync_token: Option<&str>,
+) -> Result<SyncResult, TempoError> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "https://www.googleapis.com/calendar/v3/calendars/{}/events",
+        calendar_id
+    );
+
+    let mut added_or_updated = Vec::new();
+    let mut removed_google_ids = Vec::new();
+    let mut next_sync_token = None;
+    let mut page_token: Option<String> = None;
+
+    loop {
+        let mut query: Vec<(&str, &str)> = Vec::new();
+        if let Some(token) = sync_token {
+            query.push(("syncToken", token));
+        } else {
+            if let Some(tmin) = time_min {
+                query.push(("timeMin", tmin));
+            }
+            if let Some(tmax) = time_max {
+                query.push(("timeMax", tmax));
+            }
+        }
+        query.push(("singleEvents", "true"));
+        if let Some(ref token) = page_token {
+            query.push(("pageToken", token));
+        }
+
+        let response = client
+            .get(&url)
+            .bearer_auth(access_token)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| TempoError::SubscriptionFailed(format!("GET {} failed: {}", url, e)))?;
+
+        if response.status() == reqwest::StatusCode::GONE {
+            return Err(TempoError::SubscriptionFailed(
+                "Google Calendar sync token expired (410 Gone); retry without a sync token using time_min/time_max for a full resync".to_string(),
+            ));
+        }
+
+        let response = response
+            .error_for_status()
+            .map_err(|e| TempoError::SubscriptionFailed(format!("GET {} failed: {}", url, e)))?;
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| TempoError::SubscriptionFailed(format!("reading response body: {}", e)))?;
+        let page: EventsListResponse = serde_json::from_str(&body)
+            .map_err(|e| TempoError::SubscriptionFailed(format!("parsing events.list response: {}", e)))?;
+
+        for item in page.items {
+            if item.status.as_deref() == Some("cancelled") {
+                if let Some(id) = item.id.clone() {
+                    removed_google_ids.push(id);
+                }
+                continue;
+            }
+            added_or_updated.push(gcal_event_to_event(&item)?);
+        }
+
+        if page.next_sync_token.is_some() {
+            next_sync_token = page.next_sync_token;
+        }
+        page_token = page.next_page_token;
+        if page_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(SyncResult {
+        added_or_updated,
+        removed_google_ids,
+        next_sync_token,
+    })
+}
@@ -1,67 +1,304 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
-use icalendar::{Calendar as IcalCalendar, CalendarComponent, CalendarDateTime, Component, DatePerhapsTime, EventLike};
+use std::collections::HashMap;
 
-use crate::calendar::event::{Event, EventId, RecurrenceRule};
+use chrono::{DateTime, Days, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use icalendar::{
+    Calendar as IcalCalendar, CalendarComponent, CalendarDateTime, Component, DatePerhapsTime, EventLike, Property,
+};
+
+use crate::calendar::event::{
+    Attendee, Event, EventId, EventOccurrence, EventTime, RecurrenceOverride, RecurrenceRule, Transparency, parse_iana_tz,
+};
+use crate::calendar::time_utils::{BusyPeriod, FreeBusyResult, TimeRange};
 use crate::error::TempoError;
 
-/// Parse an iCal string into domain Events.
+/// A parsed `VEVENT` plus the bookkeeping (`UID`, optional `RECURRENCE-ID`)
+/// needed to fold overrides back into their master before handing callers
+/// plain domain `Event`s.
+struct ParsedVevent {
+    event: Event,
+    uid: String,
+    recurrence_id: Option<String>,
+}
+
+/// Parse an iCal string into domain Events. A `VEVENT` that shares a `UID`
+/// with another but carries a `RECURRENCE-ID` is folded into that master
+/// event's `overrides` rather than returned as its own `Event`.
 pub fn parse_ical(ical_data: &str) -> Result<Vec<Event>, TempoError> {
     let calendar: IcalCalendar = ical_data
         .parse()
         .map_err(|e| TempoError::InvalidIcal(format!("Parse error: {}", e)))?;
 
-    let mut events = Vec::new();
+    let mut parsed = Vec::new();
     for component in &calendar.components {
         if let CalendarComponent::Event(ical_event) = component {
-            let event = ical_event_to_domain(ical_event)?;
-            events.push(event);
+            parsed.push(ical_event_to_domain(ical_event)?);
+        }
+    }
+
+    group_recurrence_overrides(parsed)
+}
+
+/// Fold `RECURRENCE-ID` components into the `overrides` map of the master
+/// event sharing their `UID`. A `RECURRENCE-ID` whose `UID` matches no master
+/// is kept as a standalone event rather than silently dropped.
+fn group_recurrence_overrides(parsed: Vec<ParsedVevent>) -> Result<Vec<Event>, TempoError> {
+    let mut uid_index: HashMap<String, usize> = HashMap::new();
+    let mut events: Vec<Event> = Vec::new();
+    let mut orphans: Vec<ParsedVevent> = Vec::new();
+
+    for item in parsed {
+        if item.recurrence_id.is_none() {
+            uid_index.insert(item.uid.clone(), events.len());
+            events.push(item.event);
+        } else {
+            orphans.push(item);
+        }
+    }
+
+    for item in orphans {
+        let recurrence_id = item.recurrence_id.as_deref().expect("filtered above");
+        match uid_index.get(&item.uid) {
+            Some(&idx) => {
+                let master = &mut events[idx];
+                let key = parse_ical_local_datetime(recurrence_id, &master.timezone, master.is_all_day())?;
+                let cancelled = item
+                    .event
+                    .metadata
+                    .get("status")
+                    .is_some_and(|s| s.eq_ignore_ascii_case("CANCELLED"));
+                master.overrides.insert(
+                    key,
+                    RecurrenceOverride {
+                        title: item.event.title,
+                        start: item.event.start,
+                        end: item.event.end,
+                        metadata: item.event.metadata,
+                        cancelled,
+                    },
+                );
+            }
+            None => events.push(item.event),
         }
     }
+
     Ok(events)
 }
 
-fn ical_event_to_domain(ical_event: &icalendar::Event) -> Result<Event, TempoError> {
+fn ical_event_to_domain(ical_event: &icalendar::Event) -> Result<ParsedVevent, TempoError> {
     let title = ical_event
         .get_summary()
         .unwrap_or("(untitled)")
         .to_string();
 
-    let start = extract_datetime(ical_event.get_start(), "DTSTART")?;
-    let end = extract_datetime(ical_event.get_end(), "DTEND")
-        .unwrap_or(start + chrono::Duration::hours(1));
+    let (start, start_tzid) = extract_event_time(ical_event.get_start(), "DTSTART")?;
+    let mut duration_based = false;
+    let end = match extract_event_time(ical_event.get_end(), "DTEND") {
+        Ok((end, _)) => {
+            // RFC 5545 §3.6.1: the item type is determined by DTSTART's value
+            // type; DTEND must agree or the event is ambiguous.
+            if end.is_all_day() != start.is_all_day() {
+                return Err(TempoError::InvalidIcal(
+                    "DTEND value type (DATE vs DATE-TIME) must match DTSTART".to_string(),
+                ));
+            }
+            adjust_ical_dtend(end)
+        }
+        Err(_) => match ical_event.property_value("DURATION") {
+            Some(duration_value) => {
+                let duration = parse_ical_duration(duration_value)?;
+                duration_based = true;
+                match start {
+                    EventTime::Date(d) => EventTime::Date(
+                        (d + duration).checked_sub_days(Days::new(1)).unwrap_or(d),
+                    ),
+                    EventTime::DateTime(dt) => EventTime::DateTime(dt + duration),
+                }
+            }
+            None => match start {
+                EventTime::Date(d) => EventTime::Date(d),
+                EventTime::DateTime(dt) => EventTime::DateTime(dt + chrono::Duration::hours(1)),
+            },
+        },
+    };
 
     let rrule = ical_event
         .property_value("RRULE")
-        .map(|s| RecurrenceRule {
-            rrule: s.to_string(),
-        });
-
-    Ok(Event {
-        id: EventId::new(),
-        title,
-        start,
-        end,
-        timezone: "UTC".to_string(),
-        recurrence: rrule,
-        metadata: Default::default(),
+        .map(|s| {
+            Ok::<_, TempoError>(RecurrenceRule {
+                rrule: s.to_string(),
+                exdates: collect_exdates(ical_event, start_tzid.as_deref(), start.is_all_day())?,
+                rdates: collect_date_list_property(ical_event, "RDATE", start_tzid.as_deref(), start.is_all_day())?,
+            })
+        })
+        .transpose()?;
+
+    let mut metadata = HashMap::new();
+    if let Some(description) = ical_event.get_description() {
+        metadata.insert("description".to_string(), description.to_string());
+    }
+    if let Some(location) = ical_event.get_location() {
+        metadata.insert("location".to_string(), location.to_string());
+    }
+    if let Some(organizer) = ical_event.property_value("ORGANIZER") {
+        metadata.insert("organizer".to_string(), organizer.to_string());
+    }
+    if let Some(status) = ical_event.property_value("STATUS") {
+        metadata.insert("status".to_string(), status.to_string());
+    }
+    // CATEGORIES is repeatable per RFC 5545, so the icalendar crate keeps it
+    // in `multi_properties` rather than `properties` even for our single line.
+    if let Some(categories) = ical_event
+        .multi_properties()
+        .get("CATEGORIES")
+        .and_then(|props| props.first())
+    {
+        metadata.insert("categories".to_string(), categories.value().to_string());
+    }
+    if duration_based {
+        // Remember that this event expressed its length as DURATION rather
+        // than DTEND, so export re-emits the same representation.
+        metadata.insert("end_representation".to_string(), "duration".to_string());
+    }
+
+    let attendees = ical_event
+        .multi_properties()
+        .get("ATTENDEE")
+        .map(|props| props.iter().map(property_to_attendee).collect())
+        .unwrap_or_default();
+
+    let uid = ical_event
+        .property_value("UID")
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| EventId::new().to_string());
+    let recurrence_id = ical_event.property_value("RECURRENCE-ID").map(|s| s.to_string());
+
+    Ok(ParsedVevent {
+        event: Event {
+            id: EventId::new(),
+            uid: Some(uid.clone()),
+            title,
+            start,
+            end,
+            timezone: start_tzid.unwrap_or_else(|| "UTC".to_string()),
+            recurrence: rrule,
+            attendees,
+            metadata,
+            overrides: HashMap::new(),
+        },
+        uid,
+        recurrence_id,
     })
 }
 
-fn extract_datetime(
+/// Collect `EXDATE` properties (RFC 5545 §3.8.5.1 allows comma-separated
+/// values and repeated lines) into the UTC instants they exclude, resolving
+/// each through its own `TZID` parameter if present, falling back to
+/// `default_tzid` (the event's `DTSTART` zone).
+fn collect_exdates(
+    ical_event: &icalendar::Event,
+    default_tzid: Option<&str>,
+    is_all_day: bool,
+) -> Result<Vec<DateTime<Utc>>, TempoError> {
+    collect_date_list_property(ical_event, "EXDATE", default_tzid, is_all_day)
+}
+
+/// Collect all instances of a comma-separated, repeatable date/date-time
+/// property (`EXDATE` or `RDATE`, both RFC 5545 §3.8.5), resolving each
+/// through its own `TZID` parameter if present, falling back to
+/// `default_tzid` (the event's `DTSTART` zone).
+fn collect_date_list_property(
+    ical_event: &icalendar::Event,
+    name: &str,
+    default_tzid: Option<&str>,
+    is_all_day: bool,
+) -> Result<Vec<DateTime<Utc>>, TempoError> {
+    let Some(props) = ical_event.multi_properties().get(name) else {
+        return Ok(Vec::new());
+    };
+
+    let mut dates = Vec::new();
+    for property in props {
+        let tzid = property
+            .params()
+            .get("TZID")
+            .map(|p| p.value().to_string())
+            .or_else(|| default_tzid.map(str::to_string));
+        for value in property.value().split(',') {
+            dates.push(parse_ical_local_datetime(value.trim(), tzid.as_deref().unwrap_or("UTC"), is_all_day)?);
+        }
+    }
+    Ok(dates)
+}
+
+/// Parse a bare iCal date/date-time value (as used by `EXDATE` and
+/// `RECURRENCE-ID`) into the UTC instant it names. A trailing `Z` marks a
+/// UTC value outright; otherwise the value is resolved through `tzid`
+/// (mirroring how `DTSTART;TZID=...` is resolved).
+fn parse_ical_local_datetime(value: &str, tzid: &str, is_all_day: bool) -> Result<DateTime<Utc>, TempoError> {
+    let invalid = || TempoError::InvalidIcal(format!("Invalid date/time value: '{}'", value));
+
+    if is_all_day {
+        let date = chrono::NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid())?;
+        return Ok(EventTime::Date(date).as_start_instant());
+    }
+    if let Some(utc_value) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc_value, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+        return Ok(naive.and_utc());
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+    match parse_iana_tz(tzid) {
+        Ok(tz) => resolve_local_datetime(tz, naive, tzid),
+        Err(_) => Ok(naive.and_utc()),
+    }
+}
+
+/// Parse an `ATTENDEE` property (`mailto:` value plus `CN`/`PARTSTAT` params)
+/// into our domain `Attendee`.
+fn property_to_attendee(property: &Property) -> Attendee {
+    let email = property
+        .value()
+        .strip_prefix("mailto:")
+        .unwrap_or(property.value())
+        .to_string();
+    let name = property.params().get("CN").map(|p| p.value().to_string());
+    let partstat = property.params().get("PARTSTAT").map(|p| p.value().to_string());
+    Attendee { email, name, partstat }
+}
+
+/// Build an `ATTENDEE` property (`mailto:` value plus `CN`/`PARTSTAT` params)
+/// from our domain `Attendee`.
+fn attendee_to_property(attendee: &Attendee) -> Property {
+    let mut property = Property::new("ATTENDEE", format!("mailto:{}", attendee.email));
+    if let Some(name) = &attendee.name {
+        property.add_parameter("CN", name);
+    }
+    if let Some(partstat) = &attendee.partstat {
+        property.add_parameter("PARTSTAT", partstat);
+    }
+    property.done()
+}
+
+/// Parse a `DatePerhapsTime` into an `EventTime` plus, for a timed value, the
+/// `TZID` it was anchored to (if any) — preserves whether it was a bare
+/// `DATE` (all-day) or a `DATE-TIME` (timed) value.
+fn extract_event_time(
     dpt: Option<DatePerhapsTime>,
     field_name: &str,
-) -> Result<DateTime<Utc>, TempoError> {
+) -> Result<(EventTime, Option<String>), TempoError> {
     match dpt {
         Some(DatePerhapsTime::DateTime(cdt)) => match cdt {
-            CalendarDateTime::Utc(utc) => Ok(utc),
-            CalendarDateTime::Floating(naive) => Ok(naive.and_utc()),
-            CalendarDateTime::WithTimezone { date_time, .. } => Ok(date_time.and_utc()),
+            CalendarDateTime::Utc(utc) => Ok((EventTime::DateTime(utc), None)),
+            // A floating time carries no zone at all; there's nothing to
+            // resolve it against, so we take it at face value as UTC.
+            CalendarDateTime::Floating(naive) => Ok((EventTime::DateTime(naive.and_utc()), None)),
+            CalendarDateTime::WithTimezone { date_time, tzid } => {
+                let tz = parse_iana_tz(&tzid)?;
+                let utc = resolve_local_datetime(tz, date_time, &tzid)?;
+                Ok((EventTime::DateTime(utc), Some(tzid)))
+            }
         },
-        Some(DatePerhapsTime::Date(d)) => {
-            // All-day event: midnight to midnight UTC
-            let naive = NaiveDateTime::new(d, chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap());
-            Ok(naive.and_utc())
-        }
+        Some(DatePerhapsTime::Date(d)) => Ok((EventTime::Date(d), None)),
         None => Err(TempoError::InvalidIcal(format!(
             "Missing {} field",
             field_name
@@ -69,20 +306,275 @@ fn extract_datetime(
     }
 }
 
-/// Export domain Events to an iCal string.
+/// Resolve a `TZID`-qualified wall-clock time to the UTC instant it names.
+/// Skipped-over times (the spring-forward gap) have no valid instant;
+/// fold-back times (the fall-back overlap) have two and we take the earlier.
+fn resolve_local_datetime(
+    tz: Tz,
+    naive: NaiveDateTime,
+    tzid: &str,
+) -> Result<DateTime<Utc>, TempoError> {
+    match tz.from_local_datetime(&naive) {
+        chrono::LocalResult::Single(dt) => Ok(dt.with_timezone(&Utc)),
+        chrono::LocalResult::Ambiguous(earlier, _later) => Ok(earlier.with_timezone(&Utc)),
+        chrono::LocalResult::None => Err(TempoError::InvalidIcal(format!(
+            "Local time {} does not exist in TZID={} (falls in a DST gap)",
+            naive, tzid
+        ))),
+    }
+}
+
+/// A bare-`DATE` `DTEND` is exclusive per RFC 5545 (the day *after* the event's
+/// last day); convert it to our inclusive end-date representation.
+fn adjust_ical_dtend(end: EventTime) -> EventTime {
+    match end {
+        EventTime::Date(d) => EventTime::Date(d.checked_sub_days(Days::new(1)).unwrap_or(d)),
+        other => other,
+    }
+}
+
+/// Parse an RFC 5545 §3.3.6 `DURATION` value (e.g. `PT30M`, `P1D`, `-PT15M`)
+/// into a `chrono::Duration`. Only the week/day/hour/minute/second forms are
+/// supported; the year/month forms from ISO 8601 aren't valid iCal durations.
+fn parse_ical_duration(value: &str) -> Result<chrono::Duration, TempoError> {
+    let invalid = || TempoError::InvalidIcal(format!("Invalid DURATION value: '{}'", value));
+
+    let (negative, rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+    let (date_part, time_part) = match rest.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (rest, None),
+    };
+
+    let mut total = chrono::Duration::zero();
+    let mut saw_any = false;
+
+    if let Some(weeks) = date_part.strip_suffix('W') {
+        let weeks: i64 = weeks.parse().map_err(|_| invalid())?;
+        total += chrono::Duration::weeks(weeks);
+        saw_any = true;
+    } else if !date_part.is_empty() {
+        let days = date_part.strip_suffix('D').ok_or_else(invalid)?;
+        let days: i64 = days.parse().map_err(|_| invalid())?;
+        total += chrono::Duration::days(days);
+        saw_any = true;
+    }
+
+    if let Some(time_part) = time_part {
+        let mut remaining = time_part;
+        if let Some((hours, rest)) = remaining.split_once('H') {
+            total += chrono::Duration::hours(hours.parse().map_err(|_| invalid())?);
+            remaining = rest;
+            saw_any = true;
+        }
+        if let Some((minutes, rest)) = remaining.split_once('M') {
+            total += chrono::Duration::minutes(minutes.parse().map_err(|_| invalid())?);
+            remaining = rest;
+            saw_any = true;
+        }
+        if let Some(seconds) = remaining.strip_suffix('S') {
+            total += chrono::Duration::seconds(seconds.parse().map_err(|_| invalid())?);
+            saw_any = true;
+        } else if !remaining.is_empty() {
+            return Err(invalid());
+        }
+    }
+
+    if !saw_any {
+        return Err(invalid());
+    }
+
+    Ok(if negative { -total } else { total })
+}
+
+/// Render a `chrono::Duration` as an RFC 5545 §3.3.6 `DURATION` value.
+fn format_ical_duration(duration: chrono::Duration) -> String {
+    let negative = duration < chrono::Duration::zero();
+    let mut seconds = duration.num_seconds().unsigned_abs();
+
+    let days = seconds / 86_400;
+    seconds %= 86_400;
+    let hours = seconds / 3_600;
+    seconds %= 3_600;
+    let minutes = seconds / 60;
+    seconds %= 60;
+
+    let mut value = String::new();
+    if negative {
+        value.push('-');
+    }
+    value.push('P');
+    if days > 0 {
+        value.push_str(&format!("{}D", days));
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 {
+        value.push('T');
+        if hours > 0 {
+            value.push_str(&format!("{}H", hours));
+        }
+        if minutes > 0 {
+            value.push_str(&format!("{}M", minutes));
+        }
+        if seconds > 0 {
+            value.push_str(&format!("{}S", seconds));
+        }
+    } else if days == 0 {
+        value.push_str("T0S");
+    }
+    value
+}
+
+/// The `UID` to export an event under: its original iCal `UID` (stored on
+/// `Event::uid` on import) when there is one, so re-exporting a loaded .ics
+/// file doesn't mint a fresh identifier every time; otherwise the event's
+/// own `EventId`.
+fn ical_uid(event: &Event) -> String {
+    event.uid.clone().unwrap_or_else(|| event.id.0.to_string())
+}
+
+/// Export domain Events to an iCal string, with a `VTIMEZONE` block for every
+/// distinct non-UTC `timezone` referenced by a `TZID` on a `DTSTART`/`DTEND`.
 pub fn events_to_ical(events: &[Event]) -> String {
     let mut cal = IcalCalendar::new();
     cal.name("Tempo Calendar");
 
+    let mut timezones_used: Vec<Tz> = Vec::new();
+
     for event in events {
+        let duration_based = event.metadata.get("end_representation").map(String::as_str) == Some("duration");
+
         let mut ical_event = icalendar::Event::new();
         ical_event.summary(&event.title);
-        ical_event.starts(event.start);
-        ical_event.ends(event.end);
-        ical_event.uid(&event.id.0.to_string());
+        match (event.start, event.end) {
+            (EventTime::Date(start_date), EventTime::Date(end_date)) => {
+                ical_event.starts(DatePerhapsTime::Date(start_date));
+                if duration_based {
+                    let days = (end_date - start_date).num_days() + 1;
+                    ical_event.add_property("DURATION", format_ical_duration(chrono::Duration::days(days)));
+                } else {
+                    // DTEND on an all-day event is exclusive per RFC 5545: the
+                    // day after the event's last (inclusive) day.
+                    let exclusive_end = end_date.checked_add_days(Days::new(1)).unwrap_or(end_date);
+                    ical_event.ends(DatePerhapsTime::Date(exclusive_end));
+                }
+            }
+            (EventTime::DateTime(start), EventTime::DateTime(end)) => {
+                if !event.timezone.eq_ignore_ascii_case("UTC") {
+                    if let Ok(tz) = parse_iana_tz(&event.timezone) {
+                        if !timezones_used.contains(&tz) {
+                            timezones_used.push(tz);
+                        }
+                    }
+                }
+                ical_event.starts(timed_value(start, &event.timezone));
+                if duration_based {
+                    ical_event.add_property("DURATION", format_ical_duration(end - start));
+                } else {
+                    ical_event.ends(timed_value(end, &event.timezone));
+                }
+            }
+            // Mixed DATE/DATE-TIME bounds shouldn't occur for a well-formed event;
+            // fall back to the resolved instants so export still round-trips.
+            (start, _) => {
+                ical_event.starts(start.as_start_instant());
+                ical_event.ends(event.end_utc());
+            }
+        }
+        ical_event.uid(&ical_uid(event));
 
         if let Some(ref recurrence) = event.recurrence {
             ical_event.add_property("RRULE", &recurrence.rrule);
+            if !recurrence.exdates.is_empty() {
+                ical_event.append_multi_property(date_list_property(
+                    "EXDATE",
+                    &recurrence.exdates,
+                    &event.timezone,
+                    event.is_all_day(),
+                ));
+            }
+            if !recurrence.rdates.is_empty() {
+                ical_event.append_multi_property(date_list_property(
+                    "RDATE",
+                    &recurrence.rdates,
+                    &event.timezone,
+                    event.is_all_day(),
+                ));
+            }
+        }
+        if let Some(description) = event.metadata.get("description") {
+            ical_event.description(description);
+        }
+        if let Some(location) = event.metadata.get("location") {
+            ical_event.location(location);
+        }
+        if let Some(organizer) = event.metadata.get("organizer") {
+            ical_event.add_property("ORGANIZER", organizer);
+        }
+        if let Some(status) = event.metadata.get("status") {
+            ical_event.add_property("STATUS", status);
+        }
+        if let Some(categories) = event.metadata.get("categories") {
+            ical_event.add_property("CATEGORIES", categories);
+        }
+        for attendee in &event.attendees {
+            ical_event.append_multi_property(attendee_to_property(attendee));
+        }
+
+        cal.push(ical_event.done());
+
+        for (recurrence_id, over) in &event.overrides {
+            cal.push(override_to_ical_event(event, *recurrence_id, over, &mut timezones_used));
+        }
+    }
+
+    let mut ics = cal.to_string();
+    if let Some(insert_at) = ics.find("BEGIN:VEVENT") {
+        let vtimezones: String = timezones_used.iter().map(|tz| vtimezone_block(*tz)).collect();
+        ics.insert_str(insert_at, &vtimezones);
+    }
+    ics
+}
+
+/// Export a range of expanded `EventOccurrence`s (e.g. from
+/// `Calendar::occurrences_in_range`) to an iCal string. Unlike
+/// `events_to_ical`, each occurrence becomes its own standalone `VEVENT` in
+/// UTC: an occurrence has already been resolved to concrete UTC instants and
+/// carries no `RRULE`/timezone of its own to re-emit.
+pub fn occurrences_to_ical(occurrences: &[EventOccurrence]) -> String {
+    let mut cal = IcalCalendar::new();
+    cal.name("Tempo Calendar");
+
+    for occ in occurrences {
+        let mut ical_event = icalendar::Event::new();
+        ical_event.summary(&occ.title);
+        if occ.is_all_day {
+            ical_event.starts(DatePerhapsTime::Date(occ.start.date_naive()));
+            ical_event.ends(DatePerhapsTime::Date(occ.end.date_naive()));
+        } else {
+            ical_event.starts(DatePerhapsTime::DateTime(CalendarDateTime::Utc(occ.start)));
+            ical_event.ends(DatePerhapsTime::DateTime(CalendarDateTime::Utc(occ.end)));
+        }
+        // Occurrences of the same recurring event share an `event_id`, so
+        // the instant disambiguates them into distinct UIDs.
+        ical_event.uid(&format!("{}-{}", occ.event_id, occ.start.timestamp()));
+
+        if let Some(description) = occ.metadata.get("description") {
+            ical_event.description(description);
+        }
+        if let Some(location) = occ.metadata.get("location") {
+            ical_event.location(location);
+        }
+        if let Some(organizer) = occ.metadata.get("organizer") {
+            ical_event.add_property("ORGANIZER", organizer);
+        }
+        if let Some(status) = occ.metadata.get("status") {
+            ical_event.add_property("STATUS", status);
+        }
+        if let Some(categories) = occ.metadata.get("categories") {
+            ical_event.add_property("CATEGORIES", categories);
         }
 
         cal.push(ical_event.done());
@@ -91,9 +583,249 @@ pub fn events_to_ical(events: &[Event]) -> String {
     cal.to_string()
 }
 
+/// Build a `VTIMEZONE` block for `tz` from its UTC offsets in January and
+/// July of a reference year, rather than encoding the full historical
+/// transition-rule table: enough for a `TZOFFSETFROM`/`TZOFFSETTO` pair that
+/// matches the zone's current DST behavior (or lack of it).
+fn vtimezone_block(tz: Tz) -> String {
+    let winter = tz_utc_offset_seconds(tz, NaiveDateTime::parse_from_str("2025-01-15T00:00:00", "%Y-%m-%dT%H:%M:%S").unwrap());
+    let summer = tz_utc_offset_seconds(tz, NaiveDateTime::parse_from_str("2025-07-15T00:00:00", "%Y-%m-%dT%H:%M:%S").unwrap());
+
+    if winter == summer {
+        return format!(
+            "BEGIN:VTIMEZONE\r\nTZID:{tz}\r\nBEGIN:STANDARD\r\nDTSTART:19700101T000000\r\nTZOFFSETFROM:{o}\r\nTZOFFSETTO:{o}\r\nEND:STANDARD\r\nEND:VTIMEZONE\r\n",
+            tz = tz,
+            o = format_utc_offset(winter),
+        );
+    }
+
+    // Northern-hemisphere zones are in standard time in January and daylight
+    // time in July; southern-hemisphere zones are the other way around.
+    let (standard, daylight) = if winter < summer {
+        (winter, summer)
+    } else {
+        (summer, winter)
+    };
+
+    format!(
+        "BEGIN:VTIMEZONE\r\nTZID:{tz}\r\n\
+         BEGIN:STANDARD\r\nDTSTART:19701101T020000\r\nTZOFFSETFROM:{daylight}\r\nTZOFFSETTO:{standard}\r\nEND:STANDARD\r\n\
+         BEGIN:DAYLIGHT\r\nDTSTART:19700301T020000\r\nTZOFFSETFROM:{standard}\r\nTZOFFSETTO:{daylight}\r\nEND:DAYLIGHT\r\n\
+         END:VTIMEZONE\r\n",
+        tz = tz,
+        standard = format_utc_offset(standard),
+        daylight = format_utc_offset(daylight),
+    )
+}
+
+fn tz_utc_offset_seconds(tz: Tz, naive_utc: NaiveDateTime) -> i32 {
+    tz.offset_from_utc_datetime(&naive_utc).fix().local_minus_utc()
+}
+
+fn format_utc_offset(offset_seconds: i32) -> String {
+    let sign = if offset_seconds < 0 { '-' } else { '+' };
+    let abs = offset_seconds.unsigned_abs();
+    format!("{}{:02}{:02}", sign, abs / 3600, (abs % 3600) / 60)
+}
+
+/// Render a resolved UTC instant as a `DatePerhapsTime` anchored to `timezone`:
+/// a `TZID`-qualified local time when it resolves to a known IANA zone, or a
+/// plain UTC `DATE-TIME` for `"UTC"` (or anything else we can't resolve).
+fn timed_value(instant: DateTime<Utc>, timezone: &str) -> DatePerhapsTime {
+    if timezone.eq_ignore_ascii_case("UTC") {
+        return DatePerhapsTime::DateTime(CalendarDateTime::Utc(instant));
+    }
+    match parse_iana_tz(timezone) {
+        Ok(tz) => DatePerhapsTime::DateTime(CalendarDateTime::WithTimezone {
+            date_time: instant.with_timezone(&tz).naive_local(),
+            tzid: timezone.to_string(),
+        }),
+        Err(_) => DatePerhapsTime::DateTime(CalendarDateTime::Utc(instant)),
+    }
+}
+
+/// Render a resolved UTC instant as the bare text form `EXDATE`/
+/// `RECURRENCE-ID` use: `YYYYMMDD` for an all-day value, otherwise a
+/// `TZID`-local or UTC-`Z` `YYYYMMDDTHHMMSS[Z]`, matching how `DTSTART` was
+/// rendered for the same event.
+fn ical_datetime_value(instant: DateTime<Utc>, timezone: &str, is_all_day: bool) -> String {
+    if is_all_day {
+        return instant.format("%Y%m%d").to_string();
+    }
+    if timezone.eq_ignore_ascii_case("UTC") {
+        return instant.format("%Y%m%dT%H%M%SZ").to_string();
+    }
+    match parse_iana_tz(timezone) {
+        Ok(tz) => instant.with_timezone(&tz).format("%Y%m%dT%H%M%S").to_string(),
+        Err(_) => instant.format("%Y%m%dT%H%M%SZ").to_string(),
+    }
+}
+
+/// Build a single `EXDATE`/`RDATE` property (RFC 5545 allows a
+/// comma-separated list of values in one property) carrying every listed
+/// instant, with a `TZID` parameter when the event isn't UTC and isn't
+/// all-day.
+fn date_list_property(name: &str, dates: &[DateTime<Utc>], timezone: &str, is_all_day: bool) -> Property {
+    let value = dates
+        .iter()
+        .map(|dt| ical_datetime_value(*dt, timezone, is_all_day))
+        .collect::<Vec<_>>()
+        .join(",");
+    let mut property = Property::new(name, value);
+    if !is_all_day && !timezone.eq_ignore_ascii_case("UTC") {
+        property.add_parameter("TZID", timezone);
+    }
+    property.done()
+}
+
+/// Build the `VEVENT` for a single `RECURRENCE-ID` override of `event`'s
+/// series: same `UID` as the master, its own `SUMMARY`/`DTSTART`/`DTEND`/
+/// metadata, and a `RECURRENCE-ID` naming the generated occurrence it replaces.
+fn override_to_ical_event(
+    event: &Event,
+    recurrence_id: DateTime<Utc>,
+    over: &RecurrenceOverride,
+    timezones_used: &mut Vec<Tz>,
+) -> CalendarComponent {
+    let mut ical_event = icalendar::Event::new();
+    ical_event.summary(&over.title);
+    match (over.start, over.end) {
+        (EventTime::Date(start_date), EventTime::Date(end_date)) => {
+            ical_event.starts(DatePerhapsTime::Date(start_date));
+            let exclusive_end = end_date.checked_add_days(Days::new(1)).unwrap_or(end_date);
+            ical_event.ends(DatePerhapsTime::Date(exclusive_end));
+        }
+        (EventTime::DateTime(start), EventTime::DateTime(end)) => {
+            if !event.timezone.eq_ignore_ascii_case("UTC") {
+                if let Ok(tz) = parse_iana_tz(&event.timezone) {
+                    if !timezones_used.contains(&tz) {
+                        timezones_used.push(tz);
+                    }
+                }
+            }
+            ical_event.starts(timed_value(start, &event.timezone));
+            ical_event.ends(timed_value(end, &event.timezone));
+        }
+        (start, _) => {
+            ical_event.starts(start.as_start_instant());
+            ical_event.ends(over.end.as_end_instant());
+        }
+    }
+    ical_event.uid(&ical_uid(event));
+
+    let mut recurrence_id_property = Property::new(
+        "RECURRENCE-ID",
+        ical_datetime_value(recurrence_id, &event.timezone, event.is_all_day()),
+    );
+    if !event.is_all_day() && !event.timezone.eq_ignore_ascii_case("UTC") {
+        recurrence_id_property.add_parameter("TZID", &event.timezone);
+    }
+    ical_event.append_multi_property(recurrence_id_property.done());
+
+    if let Some(description) = over.metadata.get("description") {
+        ical_event.description(description);
+    }
+    if let Some(location) = over.metadata.get("location") {
+        ical_event.location(location);
+    }
+    if over.cancelled {
+        ical_event.add_property("STATUS", "CANCELLED");
+    }
+
+    ical_event.done()
+}
+
+/// Render a UTC instant in the bare `YYYYMMDDTHHMMSSZ` form `VFREEBUSY`
+/// properties require (RFC 5545 says `DTSTART`/`DTEND`/`FREEBUSY` values on a
+/// `VFREEBUSY` MUST be UTC, unlike a `VEVENT`'s `DTSTART`, which may be
+/// `TZID`-local).
+fn utc_datetime_value(instant: DateTime<Utc>) -> String {
+    instant.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Fold a content line to RFC 5545's 75-octet limit: every continuation is
+/// introduced by CRLF followed by a single space, so a reader can always
+/// tell a folded continuation from the start of the next property.
+fn fold_ical_line(line: &str) -> String {
+    const LIMIT: usize = 75;
+
+    if line.len() <= LIMIT {
+        return line.to_string();
+    }
+
+    let mut folded = String::new();
+    let mut start = 0;
+    let mut first = true;
+    while start < line.len() {
+        let budget = if first { LIMIT } else { LIMIT - 1 };
+        let mut end = (start + budget).min(line.len());
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        if !first {
+            folded.push_str("\r\n ");
+        }
+        folded.push_str(&line[start..end]);
+        start = end;
+        first = false;
+    }
+    folded
+}
+
+/// Build one `FREEBUSY` property line for a merged busy/tentative `period`,
+/// using the `PERIOD` form (`FREEBUSY:start/end`) and an explicit `FBTYPE`.
+fn freebusy_property_line(period: &BusyPeriod, fbtype: &str) -> String {
+    fold_ical_line(&format!(
+        "FREEBUSY;FBTYPE={fbtype}:{}/{}",
+        utc_datetime_value(period.range.start),
+        utc_datetime_value(period.range.end),
+    ))
+}
+
+impl FreeBusyResult {
+    /// Render as a standalone `VCALENDAR` wrapping a single `VFREEBUSY`
+    /// component covering `range`, attributed to `organizer`: a `DTSTART`/
+    /// `DTEND` pair for the queried window and one `FREEBUSY` property per
+    /// merged period, `FBTYPE=BUSY` (or `BUSY-UNAVAILABLE` for an
+    /// out-of-office period) for `busy_periods` and `FBTYPE=BUSY-TENTATIVE`
+    /// for `tentative_periods`. Unlike `events_to_ical`/`occurrences_to_ical`,
+    /// `VFREEBUSY` isn't a component the `icalendar` crate builds for us, so
+    /// this assembles and folds the lines by hand.
+    pub fn to_vfreebusy(&self, range: &TimeRange, organizer: &str) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//Tempo Calendar//EN".to_string(),
+            "BEGIN:VFREEBUSY".to_string(),
+            format!("DTSTART:{}", utc_datetime_value(range.start)),
+            format!("DTEND:{}", utc_datetime_value(range.end)),
+            fold_ical_line(&format!("ORGANIZER:{organizer}")),
+        ];
+
+        for period in &self.busy_periods {
+            let fbtype = match period.transparency {
+                Transparency::OutOfOffice => "BUSY-UNAVAILABLE",
+                _ => "BUSY",
+            };
+            lines.push(freebusy_property_line(period, fbtype));
+        }
+        for period in &self.tentative_periods {
+            lines.push(freebusy_property_line(period, "BUSY-TENTATIVE"));
+        }
+
+        lines.push("END:VFREEBUSY".to_string());
+        lines.push("END:VCALENDAR".to_string());
+
+        let mut ics = lines.join("\r\n");
+        ics.push_str("\r\n");
+        ics
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::{TimeZone, Timelike};
 
     #[test]
     fn parse_simple_ical() {
@@ -109,8 +841,52 @@ mod tests {
         let events = parse_ical(ical).unwrap();
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].title, "Team Standup");
-        assert_eq!(events[0].start.hour(), 9);
-        assert_eq!(events[0].end.minute(), 30);
+        assert_eq!(events[0].start_utc().hour(), 9);
+        assert_eq!(events[0].end_utc().minute(), 30);
+    }
+
+    #[test]
+    fn parse_all_day_event_as_date() {
+        let ical = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            SUMMARY:Company Holiday\r\n\
+            DTSTART;VALUE=DATE:20250120\r\n\
+            DTEND;VALUE=DATE:20250121\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = parse_ical(ical).unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].is_all_day());
+        assert!(matches!(events[0].start, EventTime::Date(_)));
+        // DTEND;VALUE=DATE is exclusive; a single-day holiday on the 20th keeps
+        // our inclusive start == end.
+        assert_eq!(events[0].start, events[0].end);
+    }
+
+    #[test]
+    fn export_and_reparse_multi_day_all_day_event() {
+        let events = vec![Event {
+            id: EventId::new(),
+            uid: None,
+            title: "Conference".to_string(),
+            start: EventTime::Date(chrono::NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()),
+            end: EventTime::Date(chrono::NaiveDate::from_ymd_opt(2025, 3, 3).unwrap()),
+            timezone: "UTC".to_string(),
+            recurrence: None,
+            attendees: Vec::new(),
+            metadata: Default::default(),
+            overrides: HashMap::new(),
+        }];
+
+        let exported = events_to_ical(&events);
+        let reparsed = parse_ical(&exported).unwrap();
+
+        assert_eq!(reparsed.len(), 1);
+        assert!(reparsed[0].is_all_day());
+        assert_eq!(reparsed[0].start, events[0].start);
+        assert_eq!(reparsed[0].end, events[0].end);
     }
 
     #[test]
@@ -168,8 +944,219 @@ mod tests {
 
         assert_eq!(events.len(), reparsed.len());
         assert_eq!(events[0].title, reparsed[0].title);
-        assert_eq!(events[0].start, reparsed[0].start);
-        assert_eq!(events[0].end, reparsed[0].end);
+        assert_eq!(events[0].start_utc(), reparsed[0].start_utc());
+        assert_eq!(events[0].end_utc(), reparsed[0].end_utc());
+    }
+
+    #[test]
+    fn export_and_reparse_preserves_timezone_rrule_and_metadata() {
+        let mut metadata = HashMap::new();
+        metadata.insert("description".to_string(), "Weekly sync".to_string());
+        metadata.insert("location".to_string(), "Room 101".to_string());
+
+        let events = vec![Event {
+            id: EventId::new(),
+            uid: None,
+            title: "Standup".to_string(),
+            start: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 14, 0, 0).unwrap()),
+            end: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 14, 30, 0).unwrap()),
+            timezone: "America/New_York".to_string(),
+            recurrence: Some(RecurrenceRule {
+                rrule: "FREQ=WEEKLY;COUNT=3".to_string(),
+                exdates: Vec::new(),
+                rdates: Vec::new(),
+            }),
+            attendees: Vec::new(),
+            metadata,
+            overrides: HashMap::new(),
+        }];
+
+        let exported = events_to_ical(&events);
+        assert!(exported.contains("TZID=America/New_York"));
+        assert!(exported.contains("BEGIN:VTIMEZONE"));
+        assert!(exported.contains("TZID:America/New_York"));
+
+        let reparsed = parse_ical(&exported).unwrap();
+        assert_eq!(reparsed.len(), 1);
+        assert_eq!(reparsed[0].title, "Standup");
+        assert_eq!(reparsed[0].timezone, "America/New_York");
+        assert_eq!(reparsed[0].start_utc(), events[0].start_utc());
+        assert_eq!(reparsed[0].end_utc(), events[0].end_utc());
+        assert_eq!(
+            reparsed[0].recurrence.as_ref().unwrap().rrule,
+            "FREQ=WEEKLY;COUNT=3"
+        );
+        assert_eq!(
+            reparsed[0].metadata.get("description").unwrap(),
+            "Weekly sync"
+        );
+        assert_eq!(reparsed[0].metadata.get("location").unwrap(), "Room 101");
+    }
+
+    #[test]
+    fn export_and_reparse_preserves_organizer_attendees_status_and_categories() {
+        let mut metadata = HashMap::new();
+        metadata.insert("organizer".to_string(), "mailto:boss@example.com".to_string());
+        metadata.insert("status".to_string(), "CONFIRMED".to_string());
+        metadata.insert("categories".to_string(), "WORK,PLANNING".to_string());
+
+        let events = vec![Event {
+            id: EventId::new(),
+            uid: None,
+            title: "Quarterly Planning".to_string(),
+            start: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 14, 0, 0).unwrap()),
+            end: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 15, 0, 0).unwrap()),
+            timezone: "UTC".to_string(),
+            recurrence: None,
+            attendees: vec![
+                Attendee {
+                    email: "alice@example.com".to_string(),
+                    name: Some("Alice".to_string()),
+                    partstat: Some("ACCEPTED".to_string()),
+                },
+                Attendee {
+                    email: "bob@example.com".to_string(),
+                    name: None,
+                    partstat: Some("NEEDS-ACTION".to_string()),
+                },
+            ],
+            metadata,
+            overrides: HashMap::new(),
+        }];
+
+        let exported = events_to_ical(&events);
+        let reparsed = parse_ical(&exported).unwrap();
+
+        assert_eq!(reparsed.len(), 1);
+        assert_eq!(
+            reparsed[0].metadata.get("organizer").unwrap(),
+            "mailto:boss@example.com"
+        );
+        assert_eq!(reparsed[0].metadata.get("status").unwrap(), "CONFIRMED");
+        assert_eq!(
+            reparsed[0].metadata.get("categories").unwrap(),
+            "WORK,PLANNING"
+        );
+        assert_eq!(reparsed[0].attendees.len(), 2);
+        assert_eq!(reparsed[0].attendees[0].email, "alice@example.com");
+        assert_eq!(reparsed[0].attendees[0].name.as_deref(), Some("Alice"));
+        assert_eq!(reparsed[0].attendees[0].partstat.as_deref(), Some("ACCEPTED"));
+        assert_eq!(reparsed[0].attendees[1].email, "bob@example.com");
+        assert_eq!(reparsed[0].attendees[1].name, None);
+        assert_eq!(reparsed[0].attendees[1].partstat.as_deref(), Some("NEEDS-ACTION"));
+    }
+
+    #[test]
+    fn with_timezone_datetime_resolves_through_the_named_zone_not_utc() {
+        // 9am America/New_York in January (EST, UTC-5) must resolve to 14:00
+        // UTC, not 09:00 UTC as a naive "treat local time as UTC" read would.
+        let ical = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            SUMMARY:Standup\r\n\
+            DTSTART;TZID=America/New_York:20250115T090000\r\n\
+            DTEND;TZID=America/New_York:20250115T093000\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = parse_ical(ical).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].timezone, "America/New_York");
+        assert_eq!(events[0].start_utc(), Utc.with_ymd_and_hms(2025, 1, 15, 14, 0, 0).unwrap());
+        assert_eq!(events[0].end_utc(), Utc.with_ymd_and_hms(2025, 1, 15, 14, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn rejects_mismatched_dtstart_dtend_value_types() {
+        let ical = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            SUMMARY:Mismatched\r\n\
+            DTSTART;VALUE=DATE:20250120\r\n\
+            DTEND:20250120T120000Z\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let result = parse_ical(ical);
+        assert!(matches!(result, Err(TempoError::InvalidIcal(_))));
+    }
+
+    #[test]
+    fn parses_timed_duration_when_dtend_is_absent() {
+        let ical = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            SUMMARY:Quick Sync\r\n\
+            DTSTART:20250115T090000Z\r\n\
+            DURATION:PT30M\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = parse_ical(ical).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].start_utc(), Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap());
+        assert_eq!(events[0].end_utc(), Utc.with_ymd_and_hms(2025, 1, 15, 9, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn parses_all_day_duration_as_a_day_span() {
+        let ical = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            SUMMARY:Offsite\r\n\
+            DTSTART;VALUE=DATE:20250301\r\n\
+            DURATION:P2D\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = parse_ical(ical).unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].is_all_day());
+        assert_eq!(events[0].start, EventTime::Date(chrono::NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()));
+        assert_eq!(events[0].end, EventTime::Date(chrono::NaiveDate::from_ymd_opt(2025, 3, 2).unwrap()));
+    }
+
+    #[test]
+    fn rejects_unparseable_duration() {
+        let ical = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            SUMMARY:Broken\r\n\
+            DTSTART:20250115T090000Z\r\n\
+            DURATION:not-a-duration\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let result = parse_ical(ical);
+        assert!(matches!(result, Err(TempoError::InvalidIcal(_))));
+    }
+
+    #[test]
+    fn export_and_reparse_preserves_duration_representation() {
+        let events = vec![Event {
+            id: EventId::new(),
+            uid: None,
+            title: "Quick Sync".to_string(),
+            start: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap()),
+            end: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 9, 30, 0).unwrap()),
+            timezone: "UTC".to_string(),
+            recurrence: None,
+            attendees: Vec::new(),
+            metadata: HashMap::from([("end_representation".to_string(), "duration".to_string())]),
+            overrides: HashMap::new(),
+        }];
+
+        let exported = events_to_ical(&events);
+        assert!(exported.contains("DURATION:PT30M"));
+        assert!(!exported.contains("DTEND"));
+
+        let reparsed = parse_ical(&exported).unwrap();
+        assert_eq!(reparsed.len(), 1);
+        assert_eq!(reparsed[0].end_utc(), events[0].end_utc());
+        assert_eq!(
+            reparsed[0].metadata.get("end_representation").map(String::as_str),
+            Some("duration")
+        );
     }
 
     #[test]
@@ -182,5 +1169,285 @@ mod tests {
         }
     }
 
-    use chrono::Timelike;
+    #[test]
+    fn parse_ical_collects_exdate() {
+        let ical = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            SUMMARY:Daily Standup\r\n\
+            DTSTART:20250115T090000Z\r\n\
+            DTEND:20250115T091500Z\r\n\
+            RRULE:FREQ=DAILY;COUNT=5\r\n\
+            EXDATE:20250117T090000Z,20250118T090000Z\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = parse_ical(ical).unwrap();
+        assert_eq!(events.len(), 1);
+        let exdates = &events[0].recurrence.as_ref().unwrap().exdates;
+        assert_eq!(exdates.len(), 2);
+        assert!(exdates.contains(&Utc.with_ymd_and_hms(2025, 1, 17, 9, 0, 0).unwrap()));
+        assert!(exdates.contains(&Utc.with_ymd_and_hms(2025, 1, 18, 9, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn export_and_reparse_preserves_exdate() {
+        let events = vec![Event {
+            id: EventId::new(),
+            uid: None,
+            title: "Standup".to_string(),
+            start: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap()),
+            end: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 9, 15, 0).unwrap()),
+            timezone: "UTC".to_string(),
+            recurrence: Some(RecurrenceRule {
+                rrule: "FREQ=DAILY;COUNT=5".to_string(),
+                exdates: vec![Utc.with_ymd_and_hms(2025, 1, 17, 9, 0, 0).unwrap()],
+                rdates: Vec::new(),
+            }),
+            attendees: Vec::new(),
+            metadata: Default::default(),
+            overrides: HashMap::new(),
+        }];
+
+        let exported = events_to_ical(&events);
+        assert!(exported.contains("EXDATE:20250117T090000Z"));
+
+        let reparsed = parse_ical(&exported).unwrap();
+        assert_eq!(reparsed.len(), 1);
+        assert_eq!(
+            reparsed[0].recurrence.as_ref().unwrap().exdates,
+            events[0].recurrence.as_ref().unwrap().exdates
+        );
+    }
+
+    #[test]
+    fn parse_ical_folds_recurrence_id_into_master_overrides() {
+        let ical = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:series-1\r\n\
+            SUMMARY:Daily Standup\r\n\
+            DTSTART:20250115T090000Z\r\n\
+            DTEND:20250115T091500Z\r\n\
+            RRULE:FREQ=DAILY;COUNT=5\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:series-1\r\n\
+            RECURRENCE-ID:20250117T090000Z\r\n\
+            SUMMARY:Standup (moved)\r\n\
+            DTSTART:20250117T110000Z\r\n\
+            DTEND:20250117T111500Z\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = parse_ical(ical).unwrap();
+        assert_eq!(events.len(), 1);
+        let over = events[0]
+            .overrides
+            .get(&Utc.with_ymd_and_hms(2025, 1, 17, 9, 0, 0).unwrap())
+            .expect("override for the third occurrence");
+        assert_eq!(over.title, "Standup (moved)");
+        assert_eq!(over.start, EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 17, 11, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn export_and_reparse_round_trips_recurrence_id_override() {
+        let mut event = Event {
+            id: EventId::new(),
+            uid: None,
+            title: "Standup".to_string(),
+            start: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap()),
+            end: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 9, 15, 0).unwrap()),
+            timezone: "UTC".to_string(),
+            recurrence: Some(RecurrenceRule {
+                rrule: "FREQ=DAILY;COUNT=5".to_string(),
+                exdates: Vec::new(),
+                rdates: Vec::new(),
+            }),
+            attendees: Vec::new(),
+            metadata: Default::default(),
+            overrides: HashMap::new(),
+        };
+        event.overrides.insert(
+            Utc.with_ymd_and_hms(2025, 1, 17, 9, 0, 0).unwrap(),
+            RecurrenceOverride {
+                title: "Standup (moved)".to_string(),
+                start: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 17, 11, 0, 0).unwrap()),
+                end: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 17, 11, 15, 0).unwrap()),
+                metadata: Default::default(),
+                cancelled: false,
+            },
+        );
+
+        let exported = events_to_ical(&[event.clone()]);
+        assert!(exported.contains("RECURRENCE-ID:20250117T090000Z"));
+
+        let reparsed = parse_ical(&exported).unwrap();
+        assert_eq!(reparsed.len(), 1);
+        let over = reparsed[0]
+            .overrides
+            .get(&Utc.with_ymd_and_hms(2025, 1, 17, 9, 0, 0).unwrap())
+            .expect("override round-trips");
+        assert_eq!(over.title, "Standup (moved)");
+        assert_eq!(over.start, EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 17, 11, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn parse_ical_stashes_uid_on_the_event() {
+        let ical = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:external-tool-id-1\r\n\
+            SUMMARY:Standup\r\n\
+            DTSTART:20250115T090000Z\r\n\
+            DTEND:20250115T093000Z\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let events = parse_ical(ical).unwrap();
+        assert_eq!(events[0].uid.as_deref(), Some("external-tool-id-1"));
+    }
+
+    #[test]
+    fn export_reuses_the_original_uid() {
+        let events = vec![Event {
+            id: EventId::new(),
+            uid: Some("external-tool-id-1".to_string()),
+            title: "Standup".to_string(),
+            start: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap()),
+            end: EventTime::DateTime(Utc.with_ymd_and_hms(2025, 1, 15, 9, 30, 0).unwrap()),
+            timezone: "UTC".to_string(),
+            recurrence: None,
+            attendees: Vec::new(),
+            metadata: Default::default(),
+            overrides: HashMap::new(),
+        }];
+
+        let exported = events_to_ical(&events);
+        assert!(exported.contains("UID:external-tool-id-1"));
+        assert!(!exported.contains(&events[0].id.0.to_string()));
+    }
+
+    #[test]
+    fn occurrences_to_ical_exports_one_standalone_vevent_per_occurrence() {
+        let occurrences = vec![
+            EventOccurrence {
+                event_id: EventId::new(),
+                title: "Standup".to_string(),
+                start: Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap(),
+                end: Utc.with_ymd_and_hms(2025, 1, 15, 9, 15, 0).unwrap(),
+                is_recurring: true,
+                is_all_day: false,
+                transparency: Transparency::Busy,
+                metadata: HashMap::from([("location".to_string(), "Room 101".to_string())]),
+            },
+            EventOccurrence {
+                event_id: EventId::new(),
+                title: "Standup".to_string(),
+                start: Utc.with_ymd_and_hms(2025, 1, 16, 9, 0, 0).unwrap(),
+                end: Utc.with_ymd_and_hms(2025, 1, 16, 9, 15, 0).unwrap(),
+                is_recurring: true,
+                is_all_day: false,
+                transparency: Transparency::Busy,
+                metadata: Default::default(),
+            },
+        ];
+
+        let exported = occurrences_to_ical(&occurrences);
+        assert_eq!(exported.matches("BEGIN:VEVENT").count(), 2);
+        assert!(exported.contains("DTSTART:20250115T090000Z"));
+        assert!(exported.contains("DTSTART:20250116T090000Z"));
+        assert!(exported.contains("LOCATION:Room 101") || exported.contains("LOCATION:Room\\ 101"));
+        assert!(!exported.contains("RRULE"));
+
+        let reparsed = parse_ical(&exported).unwrap();
+        assert_eq!(reparsed.len(), 2);
+    }
+
+    fn busy_period(start: DateTime<Utc>, end: DateTime<Utc>, transparency: Transparency) -> BusyPeriod {
+        BusyPeriod { range: TimeRange::new(start, end), event_titles: vec!["Busy".to_string()], transparency }
+    }
+
+    #[test]
+    fn to_vfreebusy_wraps_a_vfreebusy_in_a_vcalendar() {
+        let result = FreeBusyResult {
+            busy_periods: vec![busy_period(
+                Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap(),
+                Transparency::Busy,
+            )],
+            tentative_periods: vec![],
+            free_periods: vec![],
+            total_busy_minutes: 60,
+            total_free_minutes: 0,
+        };
+        let range = TimeRange::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap(),
+        );
+
+        let ics = result.to_vfreebusy(&range, "mailto:organizer@example.com");
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("BEGIN:VFREEBUSY\r\n"));
+        assert!(ics.contains("END:VFREEBUSY\r\n"));
+        assert!(ics.contains("DTSTART:20250101T000000Z\r\n"));
+        assert!(ics.contains("DTEND:20250102T000000Z\r\n"));
+        assert!(ics.contains("ORGANIZER:mailto:organizer@example.com\r\n"));
+        assert!(ics.contains("FREEBUSY;FBTYPE=BUSY:20250101T090000Z/20250101T100000Z\r\n"));
+    }
+
+    #[test]
+    fn to_vfreebusy_separates_tentative_from_busy_and_marks_out_of_office() {
+        let result = FreeBusyResult {
+            busy_periods: vec![busy_period(
+                Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap(),
+                Transparency::OutOfOffice,
+            )],
+            tentative_periods: vec![busy_period(
+                Utc.with_ymd_and_hms(2025, 1, 1, 14, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 1, 1, 15, 0, 0).unwrap(),
+                Transparency::Tentative,
+            )],
+            free_periods: vec![],
+            total_busy_minutes: 60,
+            total_free_minutes: 0,
+        };
+        let range = TimeRange::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap(),
+        );
+
+        let ics = result.to_vfreebusy(&range, "mailto:organizer@example.com");
+
+        assert!(ics.contains("FREEBUSY;FBTYPE=BUSY-UNAVAILABLE:20250101T090000Z/20250101T100000Z\r\n"));
+        assert!(ics.contains("FREEBUSY;FBTYPE=BUSY-TENTATIVE:20250101T140000Z/20250101T150000Z\r\n"));
+    }
+
+    #[test]
+    fn to_vfreebusy_folds_lines_longer_than_75_octets() {
+        let result = FreeBusyResult {
+            busy_periods: vec![],
+            tentative_periods: vec![],
+            free_periods: vec![],
+            total_busy_minutes: 0,
+            total_free_minutes: 0,
+        };
+        let range = TimeRange::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap(),
+        );
+        let long_organizer = format!("mailto:{}@example.com", "a".repeat(100));
+
+        let ics = result.to_vfreebusy(&range, &long_organizer);
+
+        let organizer_line_start = ics.find("ORGANIZER:").unwrap();
+        let folded_break = ics[organizer_line_start..].find("\r\n ").unwrap();
+        assert!(folded_break <= 75);
+        for line in ics.split("\r\n") {
+            assert!(line.len() <= 75 || !line.starts_with(' '));
+        }
+    }
 }
@@ -0,0 +1,82 @@
+//! Fetches remote iCal feeds over HTTP with conditional-GET caching, so
+//! `refresh_calendar` can poll a subscription without re-downloading (or
+//! re-parsing) a feed that hasn't changed.
+
+use crate::error::TempoError;
+
+/// The body and cache-validator headers from a successful (`200`) fetch.
+pub struct Fetched {
+    pub body: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// The result of a conditional refresh: either the server confirmed nothing
+/// changed, or it sent a (possibly identical) new body.
+pub enum RefreshOutcome {
+    NotModified,
+    Modified(Fetched),
+}
+
+/// Fetch `url` unconditionally — used for the initial `subscribe_ical` pull.
+pub async fn fetch(url: &str) -> Result<Fetched, TempoError> {
+    let response = reqwest::get(url)
+        .await
+        .map_err(|e| TempoError::SubscriptionFailed(format!("GET {} failed: {}", url, e)))?
+        .error_for_status()
+        .map_err(|e| TempoError::SubscriptionFailed(format!("GET {} failed: {}", url, e)))?;
+    to_fetched(response).await
+}
+
+/// Conditionally fetch `url`, sending whichever of `etag`/`last_modified` is
+/// present as `If-None-Match`/`If-Modified-Since` (RFC 7232). A feed that
+/// only ever sends one of the two validators still works: we forward
+/// whichever we have and rely on `304` for the rest.
+pub async fn refresh(
+    url: &str,
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+) -> Result<RefreshOutcome, TempoError> {
+    let client = reqwest::Client::new();
+    let mut request = client.get(url);
+    if let Some(etag) = etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = last_modified {
+        request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| TempoError::SubscriptionFailed(format!("GET {} failed: {}", url, e)))?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(RefreshOutcome::NotModified);
+    }
+
+    let response = response
+        .error_for_status()
+        .map_err(|e| TempoError::SubscriptionFailed(format!("GET {} failed: {}", url, e)))?;
+
+    Ok(RefreshOutcome::Modified(to_fetched(response).await?))
+}
+
+async fn to_fetched(response: reqwest::Response) -> Result<Fetched, TempoError> {
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let last_modified = response
+        .headers()
+        .get(reqwest::header::LAST_MODIFIED)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let body = response
+        .text()
+        .await
+        .map_err(|e| TempoError::SubscriptionFailed(format!("reading response body: {}", e)))?;
+
+    Ok(Fetched { body, etag, last_modified })
+}
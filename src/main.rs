@@ -1,6 +1,9 @@
+mod caldav_sync;
 mod calendar;
 mod error;
+mod gcal_sync;
 mod ical_bridge;
+mod ical_sync;
 mod server;
 
 use anyhow::Result;
@@ -4,12 +4,21 @@ use chrono::{DateTime, Utc};
 use rmcp::{ErrorData as McpError, model::*};
 use serde::Serialize;
 
-use crate::calendar::event::{Event, EventId, RecurrenceRule};
+use crate::calendar::event::{Event, EventId, EventTime, RecurrenceRule};
 use crate::calendar::proposal::ProposedEvent;
 use crate::error::TempoError;
 use super::types::{GCalEvent, JsonEventInput};
 
 pub(crate) fn parse_datetime(s: &str) -> Result<DateTime<Utc>, TempoError> {
+    parse_datetime_in_tz(s, chrono_tz::UTC)
+}
+
+/// As `parse_datetime`, but relative anchors that name a bare calendar day
+/// (`today`, `tomorrow`) resolve to local midnight in `tz` rather than UTC.
+pub(crate) fn parse_datetime_in_tz(s: &str, tz: chrono_tz::Tz) -> Result<DateTime<Utc>, TempoError> {
+    if let Some(dt) = parse_relative_datetime(s, tz) {
+        return Ok(dt);
+    }
     // Try RFC 3339 first (with timezone offset)
     if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
         return Ok(dt.with_timezone(&Utc));
@@ -19,25 +28,130 @@ pub(crate) fn parse_datetime(s: &str) -> Result<DateTime<Utc>, TempoError> {
         return Ok(naive.and_utc());
     }
     Err(TempoError::InvalidTimeRange(format!(
-        "Cannot parse datetime: '{}'. Use ISO 8601 format.",
+        "Cannot parse datetime: '{}'. Use ISO 8601, or a relative form like 'now', 'today', 'tomorrow', '+2h'.",
         s
     )))
 }
 
+/// Parse a relative anchor (`now`, `today`, `tomorrow`) or a `+`/`-` prefixed
+/// offset from now (e.g. `+2h`, `-90m`), anchored against the current
+/// instant. Bare-day anchors resolve to local midnight in `tz`. Returns
+/// `None` if `s` isn't one of these relative forms.
+fn parse_relative_datetime(s: &str, tz: chrono_tz::Tz) -> Option<DateTime<Utc>> {
+    let now = Utc::now();
+    match s {
+        "now" => return Some(now),
+        "today" => {
+            return crate::calendar::time_utils::local_midnight_utc(
+                now.with_timezone(&tz).date_naive(),
+                tz,
+            )
+            .ok();
+        }
+        "tomorrow" => {
+            let tomorrow = now.with_timezone(&tz).date_naive().succ_opt()?;
+            return crate::calendar::time_utils::local_midnight_utc(tomorrow, tz).ok();
+        }
+        _ => {}
+    }
+
+    let (negative, rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+')?),
+    };
+    let offset = parse_duration_magnitude(rest)?;
+    Some(if negative { now - offset } else { now + offset })
+}
+
+/// Parse a bare duration magnitude like `"30m"`, `"2h"`, or `"1d"` (no sign).
+fn parse_duration_magnitude(s: &str) -> Option<chrono::TimeDelta> {
+    let unit = s.chars().last()?;
+    let digits = &s[..s.len() - unit.len_utf8()];
+    let n: i64 = digits.parse().ok()?;
+    match unit {
+        'm' => Some(chrono::TimeDelta::minutes(n)),
+        'h' => Some(chrono::TimeDelta::hours(n)),
+        'd' => Some(chrono::TimeDelta::days(n)),
+        _ => None,
+    }
+}
+
+/// A duration relative to another instant, e.g. `"+30m"` meaning "30 minutes
+/// after `start`". Used when `end` names a duration rather than an absolute
+/// time. Returns `None` if `s` isn't of this form, so callers fall back to
+/// parsing it as an absolute/relative instant.
+fn parse_duration_offset(s: &str) -> Option<chrono::TimeDelta> {
+    parse_duration_magnitude(s.strip_prefix('+')?)
+}
+
+/// Parse an IANA timezone name (e.g. `"America/New_York"`) for bucketing
+/// occurrences by local calendar day.
+pub(crate) fn parse_timezone(s: &str) -> Result<chrono_tz::Tz, TempoError> {
+    s.parse().map_err(|_| {
+        TempoError::InvalidInput(format!(
+            "Unknown IANA timezone: '{}'. Use a name like 'America/New_York'.",
+            s
+        ))
+    })
+}
+
+/// Parse a weekday abbreviation or name (`"Mon"`, `"Monday"`, case-insensitive)
+/// for registering recurring working-hours windows.
+pub(crate) fn parse_weekday(s: &str) -> Result<chrono::Weekday, TempoError> {
+    match s.to_ascii_lowercase().as_str() {
+        "mon" | "monday" => Ok(chrono::Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Ok(chrono::Weekday::Tue),
+        "wed" | "wednesday" => Ok(chrono::Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Ok(chrono::Weekday::Thu),
+        "fri" | "friday" => Ok(chrono::Weekday::Fri),
+        "sat" | "saturday" => Ok(chrono::Weekday::Sat),
+        "sun" | "sunday" => Ok(chrono::Weekday::Sun),
+        _ => Err(TempoError::InvalidInput(format!(
+            "Unknown weekday: '{}'. Use a name like 'Mon' or 'Monday'.",
+            s
+        ))),
+    }
+}
+
+/// Parse a bare `HH:MM` wall-clock time (e.g. `"09:00"`) for a working-hours window.
+pub(crate) fn parse_naive_time(s: &str) -> Result<chrono::NaiveTime, TempoError> {
+    chrono::NaiveTime::parse_from_str(s, "%H:%M").map_err(|_| {
+        TempoError::InvalidInput(format!("Cannot parse time: '{}'. Use 'HH:MM' (e.g. '09:00').", s))
+    })
+}
+
+/// Parse a bare `YYYY-MM-DD` calendar date (the form Google Calendar and
+/// iCal use for all-day events), an absolute instant, or a relative form
+/// (`now`, `today`, `tomorrow`, `+2h`) resolved against `tz`, into an
+/// `EventTime`.
+pub(crate) fn parse_event_time(s: &str, tz: chrono_tz::Tz) -> Result<EventTime, TempoError> {
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(EventTime::Date(date));
+    }
+    parse_datetime_in_tz(s, tz).map(EventTime::DateTime)
+}
+
 /// Shared parsing logic for JsonEventInput fields.
 struct ParsedEventInput {
     title: String,
-    start: DateTime<Utc>,
-    end: DateTime<Utc>,
+    start: EventTime,
+    end: EventTime,
     timezone: String,
     recurrence: Option<RecurrenceRule>,
     metadata: HashMap<String, String>,
 }
 
 fn parse_json_event_input(input: &JsonEventInput) -> Result<ParsedEventInput, TempoError> {
-    let start = parse_datetime(&input.start)?;
-    let end = parse_datetime(&input.end)?;
-    if end <= start {
+    let timezone = input.timezone.clone().unwrap_or_else(|| "UTC".to_string());
+    let tz = parse_timezone(&timezone)?;
+
+    let start = parse_event_time(&input.start, tz)?;
+    // `end` may instead be a duration relative to `start` (e.g. "+30m").
+    let end = match parse_duration_offset(&input.end) {
+        Some(duration) => EventTime::DateTime(start.as_start_instant() + duration),
+        None => parse_event_time(&input.end, tz)?,
+    };
+    if end.as_end_instant() <= start.as_start_instant() {
         return Err(TempoError::InvalidTimeRange(
             "End time must be after start time".to_string(),
         ));
@@ -46,9 +160,11 @@ fn parse_json_event_input(input: &JsonEventInput) -> Result<ParsedEventInput, Te
         title: input.title.clone(),
         start,
         end,
-        timezone: input.timezone.clone().unwrap_or_else(|| "UTC".to_string()),
+        timezone,
         recurrence: input.rrule.as_ref().map(|r| RecurrenceRule {
             rrule: r.clone(),
+            exdates: Vec::new(),
+            rdates: Vec::new(),
         }),
         metadata: input.metadata.clone().unwrap_or_default(),
     })
@@ -74,16 +190,28 @@ pub(crate) fn json_event_to_event(
     let parsed = parse_json_event_input(input)?;
     Ok(Event {
         id: EventId::new(),
+        uid: None,
         title: parsed.title,
         start: parsed.start,
         end: parsed.end,
         timezone: parsed.timezone,
         recurrence: parsed.recurrence,
+        attendees: Vec::new(),
         metadata: parsed.metadata,
+        overrides: HashMap::new(),
     })
 }
 
 pub(crate) fn gcal_event_to_event(input: &GCalEvent) -> Result<Event, TempoError> {
+    // Mirror gcal_sync::fetch_events, which filters cancelled events into
+    // removed_google_ids before conversion: a cancelled event pasted through
+    // this path should be dropped, not imported as a normal busy event.
+    if input.status.as_deref() == Some("cancelled") {
+        return Err(TempoError::InvalidInput(
+            "Event is cancelled".to_string(),
+        ));
+    }
+
     let start_str = input
         .start
         .date_time
@@ -97,9 +225,26 @@ pub(crate) fn gcal_event_to_event(input: &GCalEvent) -> Result<Event, TempoError
         .or(input.end.date.as_deref())
         .ok_or_else(|| TempoError::InvalidInput("Missing end dateTime".to_string()))?;
 
-    let start = parse_datetime(start_str)?;
-    let end = parse_datetime(end_str)?;
-    if end <= start {
+    let timezone = input
+        .start
+        .time_zone
+        .clone()
+        .unwrap_or_else(|| "UTC".to_string());
+    let tz = parse_timezone(&timezone)?;
+
+    let start = parse_event_time(start_str, tz)?;
+    let end = parse_event_time(end_str, tz)?;
+    // Google's `end.date` (unlike `end.dateTime`) is exclusive per the Calendar
+    // API; convert it to our inclusive end-date representation.
+    let end = if input.end.date_time.is_none() {
+        match end {
+            EventTime::Date(d) => EventTime::Date(d.checked_sub_days(chrono::Days::new(1)).unwrap_or(d)),
+            other => other,
+        }
+    } else {
+        end
+    };
+    if end.as_end_instant() <= start.as_start_instant() {
         return Err(TempoError::InvalidTimeRange(
             "End time must be after start time".to_string(),
         ));
@@ -109,11 +254,6 @@ pub(crate) fn gcal_event_to_event(input: &GCalEvent) -> Result<Event, TempoError
         .summary
         .clone()
         .unwrap_or_else(|| "Busy".to_string());
-    let timezone = input
-        .start
-        .time_zone
-        .clone()
-        .unwrap_or_else(|| "UTC".to_string());
 
     let mut metadata = HashMap::new();
     if let Some(ref id) = input.id {
@@ -128,12 +268,15 @@ pub(crate) fn gcal_event_to_event(input: &GCalEvent) -> Result<Event, TempoError
 
     Ok(Event {
         id: EventId::new(),
+        uid: None,
         title,
         start,
         end,
         timezone,
         recurrence: None,
+        attendees: Vec::new(),
         metadata,
+        overrides: HashMap::new(),
     })
 }
 
@@ -181,6 +324,90 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_timezone_accepts_iana_name() {
+        let tz = parse_timezone("America/New_York").unwrap();
+        assert_eq!(tz, chrono_tz::America::New_York);
+    }
+
+    #[test]
+    fn parse_timezone_rejects_unknown_name() {
+        assert!(parse_timezone("Not/A_Zone").is_err());
+    }
+
+    #[test]
+    fn parse_weekday_accepts_abbreviation_and_full_name() {
+        assert_eq!(parse_weekday("Mon").unwrap(), chrono::Weekday::Mon);
+        assert_eq!(parse_weekday("friday").unwrap(), chrono::Weekday::Fri);
+    }
+
+    #[test]
+    fn parse_weekday_rejects_unknown_name() {
+        assert!(parse_weekday("Funday").is_err());
+    }
+
+    #[test]
+    fn parse_naive_time_accepts_hh_mm() {
+        let t = parse_naive_time("09:30").unwrap();
+        assert_eq!(t, chrono::NaiveTime::from_hms_opt(9, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn parse_naive_time_rejects_invalid_format() {
+        assert!(parse_naive_time("9:30am").is_err());
+    }
+
+    #[test]
+    fn parse_datetime_relative_now() {
+        let before = Utc::now();
+        let dt = parse_datetime("now").unwrap();
+        let after = Utc::now();
+        assert!(dt >= before && dt <= after);
+    }
+
+    #[test]
+    fn parse_datetime_relative_today_is_local_midnight() {
+        let ny = chrono_tz::America::New_York;
+        let dt = parse_datetime_in_tz("today", ny).unwrap();
+        let local = dt.with_timezone(&ny);
+        assert_eq!(local.format("%H:%M:%S").to_string(), "00:00:00");
+    }
+
+    #[test]
+    fn parse_datetime_relative_tomorrow_is_one_day_after_today() {
+        let today = parse_datetime_in_tz("today", chrono_tz::UTC).unwrap();
+        let tomorrow = parse_datetime_in_tz("tomorrow", chrono_tz::UTC).unwrap();
+        assert_eq!(tomorrow - today, chrono::TimeDelta::days(1));
+    }
+
+    #[test]
+    fn parse_datetime_relative_offset() {
+        let before = Utc::now() + chrono::TimeDelta::hours(2);
+        let dt = parse_datetime("+2h").unwrap();
+        let after = Utc::now() + chrono::TimeDelta::hours(2);
+        assert!(dt >= before && dt <= after);
+    }
+
+    #[test]
+    fn parse_datetime_relative_negative_offset() {
+        let dt = parse_datetime("-90m").unwrap();
+        assert!(dt <= Utc::now());
+    }
+
+    #[test]
+    fn json_event_to_event_with_relative_start_and_duration_end() {
+        let input = JsonEventInput {
+            title: "Quick call".to_string(),
+            start: "+1h".to_string(),
+            end: "+30m".to_string(),
+            timezone: None,
+            rrule: None,
+            metadata: None,
+        };
+        let event = json_event_to_event(&input).unwrap();
+        assert_eq!(event.end_utc() - event.start_utc(), chrono::TimeDelta::minutes(30));
+    }
+
     #[test]
     fn json_event_to_event_basic() {
         let input = JsonEventInput {
@@ -252,6 +479,39 @@ mod tests {
         assert_eq!(event.metadata.get("location").unwrap(), "Room 101");
     }
 
+    #[test]
+    fn gcal_event_to_event_all_day_uses_date_field() {
+        let input = GCalEvent {
+            id: Some("gcal456".to_string()),
+            summary: Some("Company Holiday".to_string()),
+            start: super::super::types::GCalDateTime {
+                date_time: None,
+                date: Some("2025-01-20".to_string()),
+                time_zone: None,
+            },
+            end: super::super::types::GCalDateTime {
+                date_time: None,
+                date: Some("2025-01-21".to_string()),
+                time_zone: None,
+            },
+            description: None,
+            location: None,
+            status: None,
+        };
+        let event = gcal_event_to_event(&input).unwrap();
+        assert!(event.is_all_day());
+        assert_eq!(
+            event.start,
+            EventTime::Date(chrono::NaiveDate::from_ymd_opt(2025, 1, 20).unwrap())
+        );
+        // Google's end.date is exclusive (the day after); we store an inclusive
+        // end date, so a single-day holiday keeps start == end.
+        assert_eq!(
+            event.end,
+            EventTime::Date(chrono::NaiveDate::from_ymd_opt(2025, 1, 20).unwrap())
+        );
+    }
+
     #[test]
     fn gcal_event_to_event_missing_start_is_error() {
         let input = GCalEvent {
@@ -274,6 +534,28 @@ mod tests {
         assert!(gcal_event_to_event(&input).is_err());
     }
 
+    #[test]
+    fn gcal_event_to_event_cancelled_is_error() {
+        let input = GCalEvent {
+            id: Some("gcal789".to_string()),
+            summary: Some("Cancelled Meeting".to_string()),
+            start: super::super::types::GCalDateTime {
+                date_time: Some("2025-01-15T09:00:00Z".to_string()),
+                date: None,
+                time_zone: None,
+            },
+            end: super::super::types::GCalDateTime {
+                date_time: Some("2025-01-15T10:00:00Z".to_string()),
+                date: None,
+                time_zone: None,
+            },
+            description: None,
+            location: None,
+            status: Some("cancelled".to_string()),
+        };
+        assert!(gcal_event_to_event(&input).is_err());
+    }
+
     #[test]
     fn tempo_err_maps_not_found_to_resource_not_found() {
         let err = tempo_err(TempoError::CalendarNotFound("test".to_string()));
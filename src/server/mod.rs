@@ -17,11 +17,19 @@ use rmcp::{
 use serde::Serialize;
 use tokio::sync::RwLock;
 
+use crate::caldav_sync;
+use crate::calendar::CaldavPublishState;
 use crate::calendar::CalendarStore;
-use crate::calendar::event::EventId;
+use crate::calendar::ChangeKind;
+use crate::calendar::GoogleSyncState;
+use crate::calendar::SyncState;
+use crate::calendar::event::{Event, EventId};
 use crate::calendar::proposal::ProposalId;
+use crate::calendar::time_utils::WeeklyAvailability;
 use crate::error::TempoError;
+use crate::gcal_sync;
 use crate::ical_bridge;
+use crate::ical_sync;
 
 #[derive(Clone)]
 pub struct TempoServer {
@@ -44,7 +52,7 @@ impl ServerHandler for TempoServer {
                 "Tempo is a lightweight in-memory calendar server for scheduling workflows. \
                  Recommended workflow (3 steps): \
                  1) Load all calendars with load_ical/load_json/load_google_calendar (you can make multiple load calls in parallel), \
-                 2) Use find_available_slots with buffer_minutes to find open windows that already account for travel time, \
+                 2) Use find_available_slots with buffer_minutes (or location plus set_travel_matrix for itinerary-aware buffers) to find open windows that already account for travel time, \
                  3) Use propose_and_commit to propose, conflict-check, and commit in one step. \
                  If propose_and_commit reports conflicts, adjust times and retry. \
                  Use the EXACT start/end times returned by find_available_slots â€” do not invent your own times."
@@ -114,7 +122,7 @@ impl TempoServer {
         })))
     }
 
-    #[tool(description = "Load events from Google Calendar API JSON format. Accepts the events array as returned by Google Calendar's events.list API. Handles nested start/end objects with dateTime, timeZone fields, and timezone offset conversion.")]
+    #[tool(description = "Load events from Google Calendar API JSON format. Accepts the events array as returned by Google Calendar's events.list API. Handles nested start/end objects with dateTime, timeZone fields, and timezone offset conversion. Events with status 'cancelled' are skipped, not imported as busy.")]
     async fn load_google_calendar(
         &self,
         params: Parameters<LoadGoogleCalendarParams>,
@@ -149,6 +157,143 @@ impl TempoServer {
         })))
     }
 
+    #[tool(description = "Fetch events directly from the Google Calendar API (events.list) using an OAuth2 access token or service-account token, instead of requiring hand-pasted JSON. Pass time_min/time_max to bound the initial fetch; the returned sync token is stored on the calendar so later calls with the same google_calendar_id automatically do a cheap incremental fetch (including applying cancellations as deletions). Paginates through nextPageToken automatically.")]
+    async fn sync_google_calendar(
+        &self,
+        params: Parameters<SyncGoogleCalendarParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let cal_name = params.0.calendar_name.as_deref().unwrap_or("default");
+
+        let sync_token = {
+            let store = self.store.read().await;
+            store
+                .get_calendar(cal_name)
+                .and_then(|cal| cal.google_sync_state())
+                .filter(|state| state.calendar_id == params.0.google_calendar_id)
+                .and_then(|state| state.sync_token.clone())
+        };
+
+        let result = gcal_sync::fetch_events(
+            &params.0.access_token,
+            &params.0.google_calendar_id,
+            params.0.time_min.as_deref(),
+            params.0.time_max.as_deref(),
+            sync_token.as_deref(),
+        )
+        .await
+        .map_err(tempo_err)?;
+
+        let mut store = self.store.write().await;
+        let cal = store.get_or_create_calendar(cal_name);
+        let events_removed = cal.remove_events_by_google_id(&result.removed_google_ids);
+        let events_upserted = result.added_or_updated.len();
+        for event in result.added_or_updated {
+            cal.upsert_event_by_google_id(event);
+        }
+        let incremental = sync_token.is_some();
+        cal.set_google_sync_state(GoogleSyncState {
+            calendar_id: params.0.google_calendar_id.clone(),
+            sync_token: result.next_sync_token,
+        });
+
+        Ok(json_text(&serde_json::json!({
+            "calendar_name": cal_name,
+            "incremental": incremental,
+            "events_upserted": events_upserted,
+            "events_removed": events_removed,
+        })))
+    }
+
+    #[tool(description = "Subscribe a calendar to a remote iCal URL and fetch it for the first time, replacing any existing events in the calendar. Follow up later with refresh_calendar to pull updates cheaply.")]
+    async fn subscribe_ical(
+        &self,
+        params: Parameters<SubscribeIcalParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let cal_name = params.0.calendar_name.as_deref().unwrap_or("default");
+
+        let fetched = ical_sync::fetch(&params.0.url).await.map_err(tempo_err)?;
+        let events = ical_bridge::parse_ical(&fetched.body).map_err(tempo_err)?;
+        let count = events.len();
+
+        let mut store = self.store.write().await;
+        let cal = store.get_or_create_calendar(cal_name);
+        cal.replace_events(events);
+        cal.set_sync_state(SyncState::new(
+            params.0.url.clone(),
+            fetched.etag,
+            fetched.last_modified,
+            &fetched.body,
+        ));
+
+        Ok(json_text(&serde_json::json!({
+            "calendar_name": cal_name,
+            "events_loaded": count,
+            "updated": true,
+        })))
+    }
+
+    #[tool(description = "Conditionally refresh a calendar previously set up with subscribe_ical. Sends the stored ETag/Last-Modified as conditional-GET headers; if the feed hasn't changed (304, or an identical body), leaves events untouched and returns {\"updated\": false}.")]
+    async fn refresh_calendar(
+        &self,
+        params: Parameters<RefreshCalendarParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let cal_name = params.0.calendar_name.as_deref().unwrap_or("default");
+
+        let (url, etag, last_modified) = {
+            let store = self.store.read().await;
+            let cal = store
+                .get_calendar(cal_name)
+                .ok_or_else(|| tempo_err(TempoError::CalendarNotFound(cal_name.to_string())))?;
+            let sync_state = cal.sync_state().ok_or_else(|| {
+                tempo_err(TempoError::SubscriptionFailed(format!(
+                    "Calendar '{}' has no active iCal subscription; call subscribe_ical first",
+                    cal_name
+                )))
+            })?;
+            (
+                sync_state.url.clone(),
+                sync_state.etag.clone(),
+                sync_state.last_modified.clone(),
+            )
+        };
+
+        match ical_sync::refresh(&url, etag.as_deref(), last_modified.as_deref())
+            .await
+            .map_err(tempo_err)?
+        {
+            ical_sync::RefreshOutcome::NotModified => Ok(json_text(&serde_json::json!({
+                "calendar_name": cal_name,
+                "updated": false,
+            }))),
+            ical_sync::RefreshOutcome::Modified(fetched) => {
+                let mut store = self.store.write().await;
+                let cal = store.get_or_create_calendar(cal_name);
+                if cal.sync_state().is_some_and(|s| s.body_unchanged(&fetched.body)) {
+                    return Ok(json_text(&serde_json::json!({
+                        "calendar_name": cal_name,
+                        "updated": false,
+                    })));
+                }
+
+                let events = ical_bridge::parse_ical(&fetched.body).map_err(tempo_err)?;
+                let count = events.len();
+                cal.replace_events(events);
+                cal.set_sync_state(SyncState::new(
+                    url,
+                    fetched.etag,
+                    fetched.last_modified,
+                    &fetched.body,
+                ));
+
+                Ok(json_text(&serde_json::json!({
+                    "calendar_name": cal_name,
+                    "updated": true,
+                    "events_loaded": count,
+                })))
+            }
+        }
+    }
+
     // === Querying ===
 
     #[tool(description = "List all event occurrences within a time range. Expands recurring events into individual occurrences. Returns events sorted by start time.")]
@@ -167,7 +312,24 @@ impl TempoServer {
         Ok(json_text(&occs))
     }
 
-    #[tool(description = "Get free/busy analysis for a time range. Returns busy periods (with event titles), free periods, and total minutes for each.")]
+    #[tool(description = "Get a day-by-day agenda for a time range, bucketed by calendar day in the given IANA timezone. Multi-day events appear under every day they overlap. `truncated` is true if a recurring event's expansion hit an internal safety bound within the range, meaning the agenda may be missing occurrences — narrow the range and retry.")]
+    async fn get_agenda(
+        &self,
+        params: Parameters<GetAgendaParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let start = parse_datetime(&params.0.start).map_err(tempo_err)?;
+        let end = parse_datetime(&params.0.end).map_err(tempo_err)?;
+        let tz = parse_timezone(&params.0.timezone).map_err(tempo_err)?;
+
+        let store = self.store.read().await;
+        let (agenda, truncated) = store
+            .agenda(start, end, tz, params.0.calendar_name.as_deref())
+            .map_err(tempo_err)?;
+
+        Ok(json_text(&serde_json::json!({ "agenda": agenda, "truncated": truncated })))
+    }
+
+    #[tool(description = "Get free/busy analysis for a time range. Returns busy periods (with event titles), tentative periods, free periods, and total minutes for each. Transparent (free) events never block time. `truncated` is true if a recurring event's expansion hit an internal safety bound within the range, meaning the result may be missing periods — narrow the range and retry.")]
     async fn get_free_busy(
         &self,
         params: Parameters<GetFreeBusyParams>,
@@ -176,47 +338,111 @@ impl TempoServer {
         let end = parse_datetime(&params.0.end).map_err(tempo_err)?;
 
         let store = self.store.read().await;
-        let result = store
-            .free_busy(start, end, params.0.calendar_name.as_deref())
+        let (result, truncated) = store
+            .free_busy(
+                start,
+                end,
+                params.0.calendar_name.as_deref(),
+                params.0.count_tentative_as_busy.unwrap_or(false),
+            )
             .map_err(tempo_err)?;
 
-        Ok(json_text(&result))
+        Ok(json_text(&serde_json::json!({ "free_busy": result, "truncated": truncated })))
     }
 
-    #[tool(description = "Find available time slots of at least the specified duration within a time range. Returns slots sorted by start time. Use buffer_minutes to account for travel time between events.")]
+    #[tool(description = "Find available time slots of at least the specified duration within a time range. Returns slots sorted by start time, each with the adjacent locations and buffer minutes applied. Pass `location` plus travel times registered via set_travel_matrix for itinerary-aware buffers; otherwise buffer_minutes is applied flat on each side. `truncated` is true if a recurring event's expansion hit an internal safety bound within the range, meaning some slots may be missing — narrow the range and retry.")]
     async fn find_available_slots(
         &self,
         params: Parameters<FindAvailableSlotsParams>,
     ) -> Result<CallToolResult, McpError> {
         let start = parse_datetime(&params.0.start).map_err(tempo_err)?;
         let end = parse_datetime(&params.0.end).map_err(tempo_err)?;
-        let buffer = TimeDelta::minutes(params.0.buffer_minutes.unwrap_or(0) as i64);
-        let effective_duration = TimeDelta::minutes(params.0.duration_minutes as i64) + buffer + buffer;
+        let min_duration = TimeDelta::minutes(params.0.duration_minutes as i64);
+        let default_buffer = TimeDelta::minutes(params.0.buffer_minutes.unwrap_or(0) as i64);
 
         let store = self.store.read().await;
-        let raw_slots = store
-            .find_available_slots(start, end, effective_duration, params.0.calendar_name.as_deref())
+        let (slots, truncated) = store
+            .find_available_slots_with_travel(
+                start,
+                end,
+                min_duration,
+                params.0.calendar_name.as_deref(),
+                params.0.location.as_deref(),
+                default_buffer,
+            )
             .map_err(tempo_err)?;
 
-        // Shrink each slot by buffer on both sides so the caller can use times directly
-        let slots: Vec<_> = if buffer > TimeDelta::zero() {
-            raw_slots
-                .into_iter()
-                .filter_map(|s| {
-                    let shrunk_start = s.start + buffer;
-                    let shrunk_end = s.end - buffer;
-                    if shrunk_end > shrunk_start {
-                        Some(crate::calendar::time_utils::TimeRange::new(shrunk_start, shrunk_end))
-                    } else {
-                        None
-                    }
-                })
-                .collect()
+        Ok(json_text(&serde_json::json!({ "slots": slots, "truncated": truncated })))
+    }
+
+    #[tool(description = "Find time slots where multiple participants, each identified by their own calendar name, are simultaneously free — the key primitive for scheduling a meeting across calendars. By default only returns slots where every participant is free; pass allow_partial to also surface best-effort slots annotated with how many of the participants are available. `truncated` is true if a recurring event's expansion hit an internal safety bound within the range, meaning some slots may be missing — narrow the range and retry.")]
+    async fn find_mutual_free_slots(
+        &self,
+        params: Parameters<FindMutualFreeSlotsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let start = parse_datetime(&params.0.start).map_err(tempo_err)?;
+        let end = parse_datetime(&params.0.end).map_err(tempo_err)?;
+        let min_duration = TimeDelta::minutes(params.0.duration_minutes as i64);
+
+        let store = self.store.read().await;
+        if params.0.allow_partial.unwrap_or(false) {
+            let (slots, truncated) = store
+                .find_mutual_free_slots_with_counts(start, end, min_duration, &params.0.calendar_names)
+                .map_err(tempo_err)?;
+            Ok(json_text(&serde_json::json!({ "slots": slots, "truncated": truncated })))
         } else {
-            raw_slots
-        };
+            let (slots, truncated) = store
+                .find_mutual_free_slots(start, end, min_duration, &params.0.calendar_names)
+                .map_err(tempo_err)?;
+            Ok(json_text(&serde_json::json!({ "slots": slots, "truncated": truncated })))
+        }
+    }
+
+    #[tool(description = "Find available time slots of at least the specified duration, restricted to recurring per-weekday working-hours windows (e.g. 09:00-17:00 Mon-Fri) instead of the raw search range. Weekdays with no registered window (e.g. weekends) contribute no slots. `truncated` is true if a recurring event's expansion hit an internal safety bound within the range, meaning some slots may be missing — narrow the range and retry.")]
+    async fn find_available_slots_within_hours(
+        &self,
+        params: Parameters<FindAvailableSlotsWithinHoursParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let start = parse_datetime(&params.0.start).map_err(tempo_err)?;
+        let end = parse_datetime(&params.0.end).map_err(tempo_err)?;
+        let min_duration = TimeDelta::minutes(params.0.duration_minutes as i64);
+        let tz = parse_timezone(&params.0.timezone).map_err(tempo_err)?;
+
+        let mut availability = WeeklyAvailability::new(tz);
+        for window in &params.0.working_hours {
+            let weekday = parse_weekday(&window.weekday).map_err(tempo_err)?;
+            let window_start = parse_naive_time(&window.start).map_err(tempo_err)?;
+            let window_end = parse_naive_time(&window.end).map_err(tempo_err)?;
+            availability.add_window(weekday, window_start, window_end);
+        }
 
-        Ok(json_text(&slots))
+        let store = self.store.read().await;
+        let (slots, truncated) = store
+            .find_available_slots_within_hours(
+                start,
+                end,
+                min_duration,
+                params.0.calendar_name.as_deref(),
+                &availability,
+            )
+            .map_err(tempo_err)?;
+
+        Ok(json_text(&serde_json::json!({ "slots": slots, "truncated": truncated })))
+    }
+
+    #[tool(description = "Register the travel time between two locations, in minutes. find_available_slots uses this to size location-aware buffers when a `location` is given, instead of a flat buffer_minutes. Registering a pair applies symmetrically in both directions.")]
+    async fn set_travel_matrix(
+        &self,
+        params: Parameters<SetTravelMatrixParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let mut store = self.store.write().await;
+        store.set_travel_time(&params.0.from_location, &params.0.to_location, params.0.travel_minutes);
+
+        Ok(json_text(&serde_json::json!({
+            "from_location": params.0.from_location,
+            "to_location": params.0.to_location,
+            "travel_minutes": params.0.travel_minutes,
+        })))
     }
 
     // === Proposals ===
@@ -397,8 +623,7 @@ impl TempoServer {
         let cal_name = params.0.calendar_name.as_deref().unwrap_or("default");
 
         let mut store = self.store.write().await;
-        let cal = store.get_or_create_calendar(cal_name);
-        cal.add_event(event);
+        store.add_event(cal_name, event);
 
         Ok(json_text(&serde_json::json!({
             "event_id": event_id.to_string(),
@@ -421,12 +646,7 @@ impl TempoServer {
         let cal_name = params.0.calendar_name.as_deref().unwrap_or("default");
 
         let mut store = self.store.write().await;
-        let cal = store
-            .get_calendar_mut(cal_name)
-            .ok_or_else(|| tempo_err(TempoError::CalendarNotFound(cal_name.to_string())))?;
-
-        cal.remove_event(&event_id)
-            .ok_or_else(|| tempo_err(TempoError::EventNotFound(params.0.event_id.clone())))?;
+        store.remove_event(cal_name, &event_id).map_err(tempo_err)?;
 
         Ok(json_text(&serde_json::json!({ "removed": true })))
     }
@@ -439,15 +659,46 @@ impl TempoServer {
         let cal_name = params.0.calendar_name.as_deref().unwrap_or("default");
 
         let mut store = self.store.write().await;
-        let cal = store
-            .get_calendar_mut(cal_name)
-            .ok_or_else(|| tempo_err(TempoError::CalendarNotFound(cal_name.to_string())))?;
-
-        cal.clear();
+        store.clear_calendar(cal_name).map_err(tempo_err)?;
 
         Ok(json_text(&serde_json::json!({ "cleared": true })))
     }
 
+    #[tool(description = "Get calendar changes (additions/removals) since a previous call, as an alternative to re-listing events with list_events. Pass the `token` from a prior get_changes call (or omit it on the first call to see everything recorded so far) to receive only what changed since then, plus a fresh token for the next call. An event added and then removed again between two calls is omitted entirely.")]
+    async fn get_changes(
+        &self,
+        params: Parameters<GetChangesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let since_seq = match &params.0.token {
+            Some(token) => token.parse::<u64>().map_err(|e| {
+                tempo_err(TempoError::InvalidInput(format!("Invalid token: {}", e)))
+            })?,
+            None => 0,
+        };
+
+        let store = self.store.read().await;
+        let (changes, next_seq) = store.get_changes(since_seq);
+
+        let changes_json: Vec<_> = changes
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "kind": match c.kind {
+                        ChangeKind::Added => "added",
+                        ChangeKind::Removed => "removed",
+                    },
+                    "event_id": c.event_id.to_string(),
+                    "calendar": c.calendar,
+                })
+            })
+            .collect();
+
+        Ok(json_text(&serde_json::json!({
+            "changes": changes_json,
+            "token": next_seq.to_string(),
+        })))
+    }
+
     // === Export ===
 
     #[tool(description = "Export a calendar as iCal/ICS format string. Includes all events with recurrence rules.")]
@@ -468,6 +719,23 @@ impl TempoServer {
         Ok(CallToolResult::success(vec![Content::text(ical_str)]))
     }
 
+    #[tool(description = "Export occurrences within a time range as iCal/ICS format string. Unlike export_ical, recurring events are expanded into one concrete VEVENT per occurrence rather than re-exported as an RRULE.")]
+    async fn export_ical_range(
+        &self,
+        params: Parameters<ExportIcalRangeParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let start = parse_datetime(&params.0.start).map_err(tempo_err)?;
+        let end = parse_datetime(&params.0.end).map_err(tempo_err)?;
+
+        let store = self.store.read().await;
+        let occs = store
+            .occurrences_in_range(start, end, params.0.calendar_name.as_deref())
+            .map_err(tempo_err)?;
+        let ical_str = ical_bridge::occurrences_to_ical(&occs);
+
+        Ok(CallToolResult::success(vec![Content::text(ical_str)]))
+    }
+
     #[tool(description = "Export a calendar as a JSON array of events.")]
     async fn export_json(
         &self,
@@ -496,8 +764,8 @@ impl TempoServer {
             .map(|e| ExportedEvent {
                 id: e.id.to_string(),
                 title: e.title.clone(),
-                start: e.start.to_rfc3339(),
-                end: e.end.to_rfc3339(),
+                start: e.start.to_field_string(),
+                end: e.end.to_field_string(),
                 timezone: e.timezone.clone(),
                 rrule: e.recurrence.as_ref().map(|r| r.rrule.clone()),
                 metadata: e.metadata.clone(),
@@ -506,6 +774,90 @@ impl TempoServer {
 
         Ok(json_text(&exported))
     }
+
+    // === CalDAV publishing ===
+
+    #[tool(description = "Publish a calendar's events to a remote CalDAV collection: PUT each event as its own VEVENT at '<collection_url>/<uid>.ics' (using If-Match/If-None-Match so a conflicting edit made directly on the server is reported rather than overwritten), then DELETE the resources for any events removed locally since the last publish to this collection. Unlike export_ical, this writes into the remote collection rather than returning a string.")]
+    async fn publish_caldav(
+        &self,
+        params: Parameters<PublishCaldavParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let cal_name = params.0.calendar_name.as_deref().unwrap_or("default");
+        let collection_url = params.0.collection_url.trim_end_matches('/').to_string();
+
+        let (events, known_etags, removed_uids, next_seq) = {
+            let store = self.store.read().await;
+            let cal = store
+                .get_calendar(cal_name)
+                .ok_or_else(|| tempo_err(TempoError::CalendarNotFound(cal_name.to_string())))?;
+            let events: Vec<Event> = cal.events().cloned().collect();
+            let prior = cal
+                .caldav_publish_state()
+                .filter(|s| s.collection_url == collection_url);
+            let known_etags = prior.map(|s| s.etags.clone()).unwrap_or_default();
+            let since_seq = prior.map(|s| s.last_seq).unwrap_or(0);
+
+            let (changes, next_seq) = store.get_changes(since_seq);
+            let removed_uids: Vec<String> = changes
+                .iter()
+                .filter(|c| c.calendar.eq_ignore_ascii_case(cal_name) && c.kind == ChangeKind::Removed)
+                .map(|c| c.event_id.to_string())
+                .collect();
+
+            (events, known_etags, removed_uids, next_seq)
+        };
+
+        let mut new_etags: HashMap<EventId, String> = HashMap::new();
+        let mut published = Vec::with_capacity(events.len());
+        for event in &events {
+            let known = known_etags.get(&event.id).map(String::as_str);
+            let result = caldav_sync::put_event(&collection_url, event, known)
+                .await
+                .map_err(tempo_err)?;
+            match &result.etag {
+                Some(etag) => {
+                    new_etags.insert(event.id, etag.clone());
+                }
+                None => {
+                    if let Some(etag) = known_etags.get(&event.id) {
+                        new_etags.insert(event.id, etag.clone());
+                    }
+                }
+            }
+            published.push(serde_json::json!({
+                "uid": result.uid,
+                "outcome": match result.outcome {
+                    caldav_sync::PutOutcome::Created => "created",
+                    caldav_sync::PutOutcome::Updated => "updated",
+                    caldav_sync::PutOutcome::Conflict => "conflict",
+                },
+            }));
+        }
+
+        let mut deleted = Vec::with_capacity(removed_uids.len());
+        for uid in &removed_uids {
+            caldav_sync::delete_event(&collection_url, uid)
+                .await
+                .map_err(tempo_err)?;
+            deleted.push(uid.clone());
+        }
+
+        let mut store = self.store.write().await;
+        let cal = store
+            .get_calendar_mut(cal_name)
+            .ok_or_else(|| tempo_err(TempoError::CalendarNotFound(cal_name.to_string())))?;
+        cal.set_caldav_publish_state(CaldavPublishState {
+            collection_url: collection_url.clone(),
+            last_seq: next_seq,
+            etags: new_etags,
+        });
+
+        Ok(json_text(&serde_json::json!({
+            "calendar_name": cal_name,
+            "published": published,
+            "deleted": deleted,
+        })))
+    }
 }
 
 impl TempoServer {
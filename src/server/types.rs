@@ -7,9 +7,9 @@ use serde::Deserialize;
 pub(crate) struct JsonEventInput {
     #[schemars(description = "Event title")]
     pub(crate) title: String,
-    #[schemars(description = "Start time (ISO 8601, e.g. '2025-01-15T09:00:00Z')")]
+    #[schemars(description = "Start time: ISO 8601 (e.g. '2025-01-15T09:00:00Z'), a bare date ('2025-01-15') for an all-day event, or a relative form ('now', 'today', 'tomorrow', '+2h') resolved against `timezone`")]
     pub(crate) start: String,
-    #[schemars(description = "End time (ISO 8601)")]
+    #[schemars(description = "End time: same forms as `start`, plus a bare duration from `start` (e.g. '+30m', '+1h')")]
     pub(crate) end: String,
     #[schemars(description = "IANA timezone name (e.g. 'America/New_York'). Defaults to 'UTC'.")]
     pub(crate) timezone: Option<String>,
@@ -47,6 +47,18 @@ pub(crate) struct ListEventsParams {
     pub(crate) calendar_name: Option<String>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct GetAgendaParams {
+    #[schemars(description = "Start of time range (ISO 8601)")]
+    pub(crate) start: String,
+    #[schemars(description = "End of time range (ISO 8601)")]
+    pub(crate) end: String,
+    #[schemars(description = "IANA timezone name (e.g. 'America/New_York') to bucket days by")]
+    pub(crate) timezone: String,
+    #[schemars(description = "Calendar name. If omitted, queries all calendars.")]
+    pub(crate) calendar_name: Option<String>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub(crate) struct GetFreeBusyParams {
     #[schemars(description = "Start of time range (ISO 8601)")]
@@ -55,6 +67,10 @@ pub(crate) struct GetFreeBusyParams {
     pub(crate) end: String,
     #[schemars(description = "Calendar name. If omitted, considers all calendars.")]
     pub(crate) calendar_name: Option<String>,
+    #[schemars(
+        description = "If true, tentative events count as busy time for free_periods/total_free_minutes. If false (default), tentative events only appear in tentative_periods."
+    )]
+    pub(crate) count_tentative_as_busy: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -67,8 +83,62 @@ pub(crate) struct FindAvailableSlotsParams {
     pub(crate) duration_minutes: u32,
     #[schemars(description = "Calendar name. If omitted, considers all calendars.")]
     pub(crate) calendar_name: Option<String>,
-    #[schemars(description = "Buffer minutes to reserve on each side of existing events (e.g. for travel time). Returned slots will already account for these buffers. Defaults to 0.")]
+    #[schemars(description = "Flat buffer minutes to reserve on each side of existing events (e.g. for travel time). Used as the fallback when `location` is omitted, or when a location pair has no registered travel time. Defaults to 0.")]
     pub(crate) buffer_minutes: Option<u32>,
+    #[schemars(description = "Location of the meeting being scheduled (e.g. 'Room 101'). When set, leading/trailing buffers use travel times registered via set_travel_matrix between this location and each adjacent event's location, falling back to buffer_minutes for unregistered pairs.")]
+    pub(crate) location: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct FindMutualFreeSlotsParams {
+    #[schemars(description = "Start of search range (ISO 8601)")]
+    pub(crate) start: String,
+    #[schemars(description = "End of search range (ISO 8601)")]
+    pub(crate) end: String,
+    #[schemars(description = "Minimum slot duration in minutes")]
+    pub(crate) duration_minutes: u32,
+    #[schemars(description = "Calendar names, one per participant, to find mutual availability across")]
+    pub(crate) calendar_names: Vec<String>,
+    #[schemars(
+        description = "If true, also return slots where only some participants are free, each annotated with how many are available. If false (default), only return slots where every participant is free."
+    )]
+    pub(crate) allow_partial: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct WorkingHoursWindow {
+    #[schemars(description = "Weekday the window applies to, e.g. 'Mon' or 'Monday'")]
+    pub(crate) weekday: String,
+    #[schemars(description = "Window start, local wall-clock time, 'HH:MM' (e.g. '09:00')")]
+    pub(crate) start: String,
+    #[schemars(description = "Window end, local wall-clock time, 'HH:MM' (e.g. '17:00')")]
+    pub(crate) end: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct FindAvailableSlotsWithinHoursParams {
+    #[schemars(description = "Start of search range (ISO 8601)")]
+    pub(crate) start: String,
+    #[schemars(description = "End of search range (ISO 8601)")]
+    pub(crate) end: String,
+    #[schemars(description = "Minimum slot duration in minutes")]
+    pub(crate) duration_minutes: u32,
+    #[schemars(description = "Calendar name. If omitted, considers all calendars.")]
+    pub(crate) calendar_name: Option<String>,
+    #[schemars(description = "IANA timezone the working-hours windows are expressed in, e.g. 'America/New_York'")]
+    pub(crate) timezone: String,
+    #[schemars(description = "Recurring per-weekday working-hours windows. A weekday with no window has no availability that day (e.g. weekends).")]
+    pub(crate) working_hours: Vec<WorkingHoursWindow>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct SetTravelMatrixParams {
+    #[schemars(description = "One location in the pair (e.g. 'Office')")]
+    pub(crate) from_location: String,
+    #[schemars(description = "The other location in the pair (e.g. 'Airport')")]
+    pub(crate) to_location: String,
+    #[schemars(description = "Travel time between the two locations, in minutes. Registered symmetrically: applies whichever direction find_available_slots needs.")]
+    pub(crate) travel_minutes: u32,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -117,9 +187,9 @@ pub(crate) struct ProposeAndCommitParams {
 pub(crate) struct AddEventParams {
     #[schemars(description = "Event title")]
     pub(crate) title: String,
-    #[schemars(description = "Start time (ISO 8601)")]
+    #[schemars(description = "Start time: ISO 8601, a bare date for an all-day event, or a relative form ('now', 'today', 'tomorrow', '+2h') resolved against `timezone`")]
     pub(crate) start: String,
-    #[schemars(description = "End time (ISO 8601)")]
+    #[schemars(description = "End time: same forms as `start`, plus a bare duration from `start` (e.g. '+30m', '+1h')")]
     pub(crate) end: String,
     #[schemars(description = "IANA timezone (defaults to 'UTC')")]
     pub(crate) timezone: Option<String>,
@@ -145,12 +215,50 @@ pub(crate) struct ClearCalendarParams {
     pub(crate) calendar_name: Option<String>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct GetChangesParams {
+    #[schemars(description = "Opaque token from a prior get_changes call. Omit to see every change recorded so far.")]
+    pub(crate) token: Option<String>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub(crate) struct ExportParams {
     #[schemars(description = "Calendar name. Defaults to 'default'.")]
     pub(crate) calendar_name: Option<String>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ExportIcalRangeParams {
+    #[schemars(description = "Start of time range (ISO 8601)")]
+    pub(crate) start: String,
+    #[schemars(description = "End of time range (ISO 8601)")]
+    pub(crate) end: String,
+    #[schemars(description = "Calendar name. If omitted, exports all calendars.")]
+    pub(crate) calendar_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct PublishCaldavParams {
+    #[schemars(description = "Base URL of the remote CalDAV collection (e.g. 'https://caldav.example.com/calendars/me/household'). Each event is PUT to '<collection_url>/<uid>.ics'.")]
+    pub(crate) collection_url: String,
+    #[schemars(description = "Calendar name to publish. Defaults to 'default'.")]
+    pub(crate) calendar_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct SubscribeIcalParams {
+    #[schemars(description = "URL of a remote iCal/ICS feed to fetch")]
+    pub(crate) url: String,
+    #[schemars(description = "Calendar name. Creates or replaces the events of an existing one. Defaults to 'default'.")]
+    pub(crate) calendar_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct RefreshCalendarParams {
+    #[schemars(description = "Calendar name previously set up with subscribe_ical. Defaults to 'default'.")]
+    pub(crate) calendar_name: Option<String>,
+}
+
 // -- Google Calendar API types --
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -179,11 +287,24 @@ pub(crate) struct GCalEvent {
     pub(crate) description: Option<String>,
     #[schemars(description = "Event location")]
     pub(crate) location: Option<String>,
-    #[schemars(description = "Event status")]
-    #[allow(dead_code)] // accepted from Google Calendar API but not used
+    #[schemars(description = "Event status (e.g. 'confirmed', 'cancelled')")]
     pub(crate) status: Option<String>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct SyncGoogleCalendarParams {
+    #[schemars(description = "OAuth2 access token (or a service-account-issued token) with Calendar API read access")]
+    pub(crate) access_token: String,
+    #[schemars(description = "Google Calendar ID to sync (e.g. 'primary' or a calendar's email-like ID)")]
+    pub(crate) google_calendar_id: String,
+    #[schemars(description = "Start of the window to fetch (RFC 3339). Ignored on an incremental sync (once a sync token has been stored from a prior call).")]
+    pub(crate) time_min: Option<String>,
+    #[schemars(description = "End of the window to fetch (RFC 3339). Ignored on an incremental sync.")]
+    pub(crate) time_max: Option<String>,
+    #[schemars(description = "Tempo calendar name to sync into. Defaults to 'default'.")]
+    pub(crate) calendar_name: Option<String>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub(crate) struct LoadGoogleCalendarParams {
     #[schemars(description = "Array of Google Calendar event objects (as returned by the Google Calendar API). Each event has summary, start: {dateTime, timeZone}, end: {dateTime, timeZone}.")]